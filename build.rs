@@ -0,0 +1,65 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+
+const MAX_LEVEL: &str = "ROGU_MAX_LEVEL";
+const RELEASE_MAX_LEVEL: &str = "ROGU_RELEASE_MAX_LEVEL";
+const RING_SLOTS: &str = "ROGU_RING_SLOTS";
+const RING_SLOT_SIZE: &str = "ROGU_RING_SLOT_SIZE";
+
+//Mirrors `LevelFilter` parsing, which is not available to build script.
+fn parse(var: &str, text: &str) -> u8 {
+    let is_extended = env::var_os("CARGO_FEATURE_EXTENDED_LEVELS").is_some();
+    let text = text.trim().to_ascii_lowercase();
+    let level = match text.as_str() {
+        "off" | "none" | "0" => Some(0),
+        "fatal" | "1" => Some(1),
+        "critical" | "2" if is_extended => Some(2),
+        "error" | "3" => Some(3),
+        "warn" | "warning" | "4" => Some(4),
+        "notice" | "5" if is_extended => Some(5),
+        "info" | "6" => Some(6),
+        "debug" | "7" => Some(7),
+        "trace" | "8" => Some(8),
+        _ => None,
+    };
+
+    match level {
+        Some(level) => level,
+        None => panic!("{}={:?} is not valid log level", var, text),
+    }
+}
+
+fn parse_size(var: &str, default: usize) -> usize {
+    match env::var(var) {
+        Ok(text) => match text.trim().parse() {
+            Ok(size) if size > 0 => size,
+            _ => panic!("{}={:?} is not valid positive number", var, text),
+        },
+        Err(_) => default,
+    }
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rerun-if-env-changed={}", MAX_LEVEL);
+    println!("cargo:rerun-if-env-changed={}", RELEASE_MAX_LEVEL);
+    println!("cargo:rerun-if-env-changed={}", RING_SLOTS);
+    println!("cargo:rerun-if-env-changed={}", RING_SLOT_SIZE);
+
+    let mut level = env::var(MAX_LEVEL).ok().map(|text| parse(MAX_LEVEL, &text));
+    if env::var_os("CARGO_CFG_DEBUG_ASSERTIONS").is_none() {
+        if let Ok(text) = env::var(RELEASE_MAX_LEVEL) {
+            level = Some(parse(RELEASE_MAX_LEVEL, &text));
+        }
+    }
+
+    let out_dir = env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo");
+    let out = Path::new(&out_dir).join("static_max_level.rs");
+    let code = format!("const STATIC_MAX_LEVEL_RAW: u8 = {};\n", level.unwrap_or(8));
+    fs::write(out, code).expect("Unable to write static_max_level.rs");
+
+    let out = Path::new(&out_dir).join("ring_size.rs");
+    let code = format!("const RING_SLOTS: usize = {};\nconst RING_SLOT_SIZE: usize = {};\n", parse_size(RING_SLOTS, 64), parse_size(RING_SLOT_SIZE, 128));
+    fs::write(out, code).expect("Unable to write ring_size.rs");
+}
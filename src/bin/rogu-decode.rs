@@ -0,0 +1,78 @@
+//!Decodes binary frames into text.
+//!
+//!Usage: `rogu-decode <firmware.elf> [frames.bin]`
+//!
+//!Frames are read from stdin, if file is not specified.
+
+use rogu::binary::decode::Decoder;
+
+use std::io::{self, Read, Write};
+use std::{env, fs, process};
+
+fn main() {
+    let mut args = env::args().skip(1);
+    let elf = match args.next() {
+        Some(elf) => elf,
+        None => {
+            eprintln!("Usage: rogu-decode <firmware.elf> [frames.bin]");
+            process::exit(2);
+        }
+    };
+
+    let elf = match fs::read(&elf) {
+        Ok(elf) => elf,
+        Err(error) => {
+            eprintln!("{}: {}", elf, error);
+            process::exit(1);
+        }
+    };
+    let decoder = match Decoder::from_elf(&elf) {
+        Some(decoder) => decoder,
+        None => {
+            eprintln!("ELF file is malformed or has no '{}' section", rogu::binary::SECTION);
+            process::exit(1);
+        }
+    };
+
+    let mut input: Box<dyn Read> = match args.next() {
+        Some(path) => match fs::File::open(&path) {
+            Ok(file) => Box::new(file),
+            Err(error) => {
+                eprintln!("{}: {}", path, error);
+                process::exit(1);
+            }
+        },
+        None => Box::new(io::stdin()),
+    };
+
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    let mut pending = Vec::new();
+    let mut buffer = [0u8; 4096];
+
+    loop {
+        let read = match input.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(read) => read,
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => {
+                eprintln!("Failed to read input: {}", error);
+                process::exit(1);
+            }
+        };
+        pending.extend_from_slice(&buffer[..read]);
+
+        let (records, consumed) = decoder.decode_all(&pending);
+        for record in records {
+            let _ = match record {
+                Ok(text) => writeln!(stdout, "{}", text),
+                Err(error) => writeln!(stdout, "<{}>", error),
+            };
+        }
+        pending.drain(..consumed);
+    }
+
+    if !pending.is_empty() {
+        eprintln!("{} trailing bytes of incomplete frame", pending.len());
+    }
+}
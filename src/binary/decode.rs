@@ -0,0 +1,277 @@
+//!Host side decoding of binary frames.
+
+use super::{tag, SEPARATOR, SECTION};
+
+use std::string::String;
+use std::vec::Vec;
+use core::fmt::{self, Write};
+use core::convert::TryFrom;
+
+#[derive(Debug, PartialEq, Eq)]
+///Decoding error
+pub enum Error {
+    ///Not enough bytes for the whole frame.
+    Incomplete,
+    ///String index is not within interned strings.
+    UnknownIndex(usize),
+    ///Frame contains invalid data.
+    Malformed,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Incomplete => fmt.write_str("incomplete frame"),
+            Error::UnknownIndex(idx) => write!(fmt, "unknown string index {}", idx),
+            Error::Malformed => fmt.write_str("malformed frame"),
+        }
+    }
+}
+
+///Returns content of section `name` within ELF file.
+///
+///Returns `None` if there is no such section or ELF file is malformed.
+pub fn elf_section<'a>(elf: &'a [u8], name: &str) -> Option<&'a [u8]> {
+    if elf.get(..4)? != b"\x7fELF" {
+        return None;
+    }
+
+    let is_64 = *elf.get(4)? == 2;
+    let is_le = *elf.get(5)? == 1;
+
+    let read = |offset: usize, size: usize| -> Option<u64> {
+        let bytes = elf.get(offset..offset.checked_add(size)?)?;
+        let mut result = 0u64;
+        for idx in 0..size {
+            let byte = match is_le {
+                true => bytes[size - 1 - idx],
+                false => bytes[idx],
+            };
+            result = (result << 8) | byte as u64;
+        }
+        Some(result)
+    };
+    let word = if is_64 { 8 } else { 4 };
+
+    let (shoff, shentsize, shnum, shstrndx) = match is_64 {
+        true => (read(0x28, 8)?, read(0x3a, 2)?, read(0x3c, 2)?, read(0x3e, 2)?),
+        false => (read(0x20, 4)?, read(0x2e, 2)?, read(0x30, 2)?, read(0x32, 2)?),
+    };
+
+    //Returns (name, type, offset, size)
+    let section = |idx: u64| -> Option<(u64, u64, usize, usize)> {
+        let header = usize::try_from(shoff.checked_add(idx.checked_mul(shentsize)?)?).ok()?;
+        let name = read(header, 4)?;
+        let typ = read(header.checked_add(4)?, 4)?;
+        let offset = read(header.checked_add(8 + 2 * word)?, word)?;
+        let size = read(header.checked_add(8 + 3 * word)?, word)?;
+        Some((name, typ, usize::try_from(offset).ok()?, usize::try_from(size).ok()?))
+    };
+
+    let (_, _, strtab_offset, strtab_size) = section(shstrndx)?;
+    let strtab = elf.get(strtab_offset..strtab_offset.checked_add(strtab_size)?)?;
+
+    for idx in 0..shnum {
+        let (name_offset, typ, offset, size) = section(idx)?;
+        let section_name = strtab.get(usize::try_from(name_offset).ok()?..)?;
+        let section_name = &section_name[..section_name.iter().position(|byte| *byte == 0)?];
+
+        //SHT_NOBITS has no content
+        if section_name == name.as_bytes() && typ != 8 {
+            return elf.get(offset..offset.checked_add(size)?);
+        }
+    }
+
+    None
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if self.bytes.len() < len {
+            return Err(Error::Malformed);
+        }
+        let (result, rest) = self.bytes.split_at(len);
+        self.bytes = rest;
+        Ok(result)
+    }
+
+    fn varint(&mut self) -> Result<usize, Error> {
+        let mut result = 0usize;
+        let mut shift = 0;
+        loop {
+            let byte = self.take(1)?[0];
+            result |= ((byte & 0x7f) as usize).checked_shl(shift).ok_or(Error::Malformed)?;
+            if byte & 0x80 == 0 {
+                return Ok(result);
+            }
+            shift += 7;
+        }
+    }
+}
+
+enum Arg<'a> {
+    Unsigned(u64),
+    Signed(i64),
+    Float(f64),
+    Bool(bool),
+    Char(char),
+    Str(&'a str),
+}
+
+impl Arg<'_> {
+    fn read<'a>(reader: &mut Reader<'a>) -> Result<Arg<'a>, Error> {
+        macro_rules! read {
+            ($ty:ty) => {{
+                let mut bytes = [0u8; core::mem::size_of::<$ty>()];
+                bytes.copy_from_slice(reader.take(core::mem::size_of::<$ty>())?);
+                <$ty>::from_le_bytes(bytes)
+            }}
+        }
+
+        let arg = match reader.take(1)?[0] {
+            tag::U8 => Arg::Unsigned(read!(u8) as u64),
+            tag::U16 => Arg::Unsigned(read!(u16) as u64),
+            tag::U32 => Arg::Unsigned(read!(u32) as u64),
+            tag::U64 => Arg::Unsigned(read!(u64)),
+            tag::I8 => Arg::Signed(read!(i8) as i64),
+            tag::I16 => Arg::Signed(read!(i16) as i64),
+            tag::I32 => Arg::Signed(read!(i32) as i64),
+            tag::I64 => Arg::Signed(read!(i64)),
+            tag::F32 => Arg::Float(read!(f32) as f64),
+            tag::F64 => Arg::Float(read!(f64)),
+            tag::BOOL => Arg::Bool(read!(u8) != 0),
+            tag::CHAR => Arg::Char(core::char::from_u32(read!(u32)).ok_or(Error::Malformed)?),
+            tag::STR => {
+                let len = reader.varint()?;
+                Arg::Str(core::str::from_utf8(reader.take(len)?).map_err(|_| Error::Malformed)?)
+            },
+            _ => return Err(Error::Malformed),
+        };
+
+        Ok(arg)
+    }
+
+    fn write(&self, out: &mut String, spec: &str) {
+        let _ = match (self, spec) {
+            (Arg::Unsigned(value), "x") => write!(out, "{:x}", value),
+            (Arg::Unsigned(value), "X") => write!(out, "{:X}", value),
+            (Arg::Signed(value), "x") => write!(out, "{:x}", value),
+            (Arg::Signed(value), "X") => write!(out, "{:X}", value),
+            (Arg::Unsigned(value), _) => write!(out, "{}", value),
+            (Arg::Signed(value), _) => write!(out, "{}", value),
+            (Arg::Float(value), "?") => write!(out, "{:?}", value),
+            (Arg::Float(value), _) => write!(out, "{}", value),
+            (Arg::Bool(value), _) => write!(out, "{}", value),
+            (Arg::Char(value), "?") => write!(out, "{:?}", value),
+            (Arg::Char(value), _) => write!(out, "{}", value),
+            (Arg::Str(value), "?") => write!(out, "{:?}", value),
+            (Arg::Str(value), _) => write!(out, "{}", value),
+        };
+    }
+}
+
+///Decoder of binary frames.
+pub struct Decoder<'a> {
+    strings: &'a [u8],
+}
+
+impl<'a> Decoder<'a> {
+    #[inline]
+    ///Creates new decoder from content of interned strings section.
+    pub fn new(strings: &'a [u8]) -> Self {
+        Self {
+            strings,
+        }
+    }
+
+    #[inline]
+    ///Creates new decoder from ELF file, returning `None` if it has no interned strings.
+    pub fn from_elf(elf: &'a [u8]) -> Option<Self> {
+        elf_section(elf, SECTION).map(Self::new)
+    }
+
+    fn string(&self, index: usize) -> Result<&'a str, Error> {
+        let string = self.strings.get(index..).ok_or(Error::UnknownIndex(index))?;
+        let len = string.iter().position(|byte| *byte == 0).ok_or(Error::UnknownIndex(index))?;
+        core::str::from_utf8(&string[..len]).map_err(|_| Error::UnknownIndex(index))
+    }
+
+    ///Decodes frame at the start of `bytes`, returning its text and number of consumed bytes.
+    ///
+    ///Text is formatted the same way as text loggers do, but without trailing newline.
+    pub fn decode(&self, bytes: &[u8]) -> Result<(String, usize), Error> {
+        let len = match bytes.get(..2) {
+            Some(len) => u16::from_le_bytes([len[0], len[1]]) as usize,
+            None => return Err(Error::Incomplete),
+        };
+        let frame = bytes.get(2..2 + len).ok_or(Error::Incomplete)?;
+
+        let mut reader = Reader {
+            bytes: frame,
+        };
+        let index = reader.varint()?;
+        let mut parts = self.string(index)?.splitn(4, SEPARATOR);
+        let (level, file, line, format) = match (parts.next(), parts.next(), parts.next(), parts.next()) {
+            (Some(level), Some(file), Some(line), Some(format)) => (level, file, line, format),
+            _ => return Err(Error::UnknownIndex(index)),
+        };
+
+        let mut out = String::new();
+        //Padded as level prefix of text loggers
+        let _ = write!(out, "{:<5} - [{}:{}] - ", level, file, line);
+
+        let mut format = format;
+        while let Some(idx) = format.find(['{', '}']) {
+            out.push_str(&format[..idx]);
+            let rest = &format[idx..];
+
+            if rest.starts_with("{{") || rest.starts_with("}}") {
+                out.push_str(&rest[..1]);
+                format = &rest[2..];
+            } else if rest.starts_with('{') {
+                let end = rest.find('}').ok_or(Error::Malformed)?;
+                let spec = rest[1..end].trim_start_matches(':');
+                match reader.bytes.is_empty() {
+                    //Truncated arguments
+                    true => out.push('…'),
+                    false => Arg::read(&mut reader)?.write(&mut out, spec),
+                }
+                format = &rest[end + 1..];
+            } else {
+                out.push('}');
+                format = &rest[1..];
+            }
+        }
+        out.push_str(format);
+
+        Ok((out, 2 + len))
+    }
+
+    ///Decodes all complete frames within `bytes`, returning them and number of consumed bytes.
+    pub fn decode_all(&self, bytes: &[u8]) -> (Vec<Result<String, Error>>, usize) {
+        let mut result = Vec::new();
+        let mut consumed = 0;
+
+        loop {
+            match self.decode(&bytes[consumed..]) {
+                Ok((text, len)) => {
+                    result.push(Ok(text));
+                    consumed += len;
+                },
+                Err(Error::Incomplete) => break,
+                Err(error) => {
+                    //Length is still valid, so skip the whole frame.
+                    let len = u16::from_le_bytes([bytes[consumed], bytes[consumed + 1]]) as usize;
+                    result.push(Err(error));
+                    consumed += 2 + len;
+                }
+            }
+        }
+
+        (result, consumed)
+    }
+}
@@ -0,0 +1,170 @@
+use super::tag;
+
+#[cfg(any(target_os = "macos", target_os = "ios", windows))]
+compile_error!("binary feature is supported only on ELF targets");
+
+///Maximum size of single frame, including length.
+///
+///Arguments, that do not fit, are discarded, while strings are truncated.
+pub const FRAME_CAPACITY: usize = 256;
+
+extern "C" {
+    //Provided by linker for sections with C identifier name.
+    #[link_name = "__start_rogu_fmt"]
+    static SECTION_START: u8;
+}
+
+#[doc(hidden)]
+///Copies interned string into array, to be placed in linker section.
+pub const fn intern<const N: usize>(text: &str) -> [u8; N] {
+    let bytes = text.as_bytes();
+    let mut result = [0; N];
+    let mut idx = 0;
+    while idx < N {
+        result[idx] = bytes[idx];
+        idx += 1;
+    }
+    result
+}
+
+///Binary frame of single record.
+pub struct Frame {
+    buffer: [u8; FRAME_CAPACITY],
+    len: usize,
+    truncated: bool,
+}
+
+impl Frame {
+    #[doc(hidden)]
+    ///Starts new frame for interned string.
+    pub fn new(interned: &'static [u8]) -> Self {
+        #[allow(unused_unsafe)]
+        let start = unsafe {
+            core::ptr::addr_of!(SECTION_START) as usize
+        };
+
+        let mut res = Self {
+            buffer: [0; FRAME_CAPACITY],
+            len: 2,
+            truncated: false,
+        };
+        res.write_varint(interned.as_ptr() as usize - start);
+        res
+    }
+
+    #[inline]
+    fn write_varint(&mut self, mut value: usize) {
+        loop {
+            let byte = (value & 0x7f) as u8;
+            value >>= 7;
+            match value {
+                0 => {
+                    self.write(&[byte]);
+                    break;
+                },
+                _ => self.write(&[byte | 0x80]),
+            }
+        }
+    }
+
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        self.buffer[self.len..self.len + bytes.len()].copy_from_slice(bytes);
+        self.len += bytes.len();
+    }
+
+    #[inline]
+    fn reserve(&mut self, len: usize) -> bool {
+        //Once argument is discarded, the rest must be discarded too, to keep arguments in order.
+        self.truncated = self.truncated || self.len + len > FRAME_CAPACITY;
+        !self.truncated
+    }
+
+    #[inline]
+    ///Writes argument with its tag.
+    pub fn write_arg(&mut self, tag: u8, bytes: &[u8]) {
+        if self.reserve(1 + bytes.len()) {
+            self.write(&[tag]);
+            self.write(bytes);
+        }
+    }
+
+    ///Writes string argument, truncating it if necessary.
+    pub fn write_str(&mut self, text: &str) {
+        //Tag and at most 2 bytes of length
+        if self.reserve(3) {
+            let mut len = core::cmp::min(FRAME_CAPACITY - self.len - 3, text.len());
+            while !text.is_char_boundary(len) {
+                len -= 1;
+            }
+
+            self.write(&[tag::STR]);
+            self.write_varint(len);
+            self.write(&text.as_bytes()[..len]);
+        }
+    }
+
+    #[doc(hidden)]
+    ///Writes frame into hook.
+    pub fn finish(mut self) {
+        let len = (self.len - 2) as u16;
+        self.buffer[..2].copy_from_slice(&len.to_le_bytes());
+        crate::out::hook::write(&self.buffer[..self.len]);
+    }
+}
+
+///Type that can be encoded into binary frame.
+pub trait Encode {
+    ///Writes self into frame
+    fn encode(&self, frame: &mut Frame);
+}
+
+impl<T: Encode + ?Sized> Encode for &T {
+    #[inline(always)]
+    fn encode(&self, frame: &mut Frame) {
+        T::encode(self, frame)
+    }
+}
+
+macro_rules! impl_encode {
+    ($($ty:ty => $tag:ident as $as:ty,)*) => {
+        $(
+            impl Encode for $ty {
+                #[inline(always)]
+                fn encode(&self, frame: &mut Frame) {
+                    frame.write_arg(tag::$tag, &(*self as $as).to_le_bytes());
+                }
+            }
+        )*
+    }
+}
+
+impl_encode!(
+    u8 => U8 as u8,
+    u16 => U16 as u16,
+    u32 => U32 as u32,
+    u64 => U64 as u64,
+    usize => U64 as u64,
+    i8 => I8 as i8,
+    i16 => I16 as i16,
+    i32 => I32 as i32,
+    i64 => I64 as i64,
+    isize => I64 as i64,
+    f32 => F32 as f32,
+    f64 => F64 as f64,
+    char => CHAR as u32,
+);
+
+impl Encode for bool {
+    #[inline(always)]
+    fn encode(&self, frame: &mut Frame) {
+        frame.write_arg(tag::BOOL, &[*self as u8]);
+    }
+}
+
+impl Encode for str {
+    #[inline(always)]
+    fn encode(&self, frame: &mut Frame) {
+        frame.write_str(self);
+    }
+}
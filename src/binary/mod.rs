@@ -0,0 +1,72 @@
+//!Deferred binary encoding.
+//!
+//!With `binary` feature, macros do not format records on device.
+//!Instead format string, together with level and location, is interned into `rogu_fmt` linker section
+//!and record is written to the [write hook](../fn.set_write_hook.html) as compact frame:
+//!
+//!```text
+//![frame length: u16 LE][string index: LEB128][argument tag: u8][argument bytes]...
+//!```
+//!
+//!String index is offset of interned string within `rogu_fmt` section.
+//!Arguments are written as raw little-endian bytes, except strings, which are prefixed with LEB128 length.
+//!
+//!Frames are turned back into text on host by `rogu-decode` binary (requires `decoder` feature), which reads
+//!`rogu_fmt` section from ELF file of the firmware:
+//!
+//!```text
+//!rogu-decode firmware.elf < frames.bin
+//!```
+//!
+//!Limitations:
+//!
+//!- Only ELF targets are supported;
+//!- Format string must be literal and can only use positional `{}`, `{:?}`, `{:x}`, `{:X}` placeholders;
+//!- Arguments must implement [Encode](trait.Encode.html), which is implemented for primitive types and strings;
+//!- Records written by macros are not passed to [sinks](../sink/index.html);
+
+#[cfg(all(feature = "binary", any(feature = "ufmt", feature = "log_facade")))]
+compile_error!("binary feature cannot be used together with ufmt or log_facade");
+
+#[cfg(feature = "binary")]
+mod encode;
+#[cfg(feature = "binary")]
+pub use encode::*;
+#[cfg(feature = "decoder")]
+pub mod decode;
+
+///Name of linker section with interned strings.
+pub const SECTION: &str = "rogu_fmt";
+
+///Separator of level, file, line and format string within interned string.
+pub const SEPARATOR: char = '\x1f';
+
+///Argument type tags.
+pub mod tag {
+    ///`u8`
+    pub const U8: u8 = 1;
+    ///`u16`
+    pub const U16: u8 = 2;
+    ///`u32`
+    pub const U32: u8 = 3;
+    ///`u64`
+    pub const U64: u8 = 4;
+    ///`i8`
+    pub const I8: u8 = 5;
+    ///`i16`
+    pub const I16: u8 = 6;
+    ///`i32`
+    pub const I32: u8 = 7;
+    ///`i64`
+    pub const I64: u8 = 8;
+    ///`f32`
+    pub const F32: u8 = 9;
+    ///`f64`
+    pub const F64: u8 = 10;
+    ///`bool`
+    pub const BOOL: u8 = 11;
+    ///`char`, encoded as `u32`
+    pub const CHAR: u8 = 12;
+    ///`str`, encoded as LEB128 length followed by bytes.
+    pub const STR: u8 = 13;
+}
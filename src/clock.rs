@@ -0,0 +1,124 @@
+//!Time source of records.
+//!
+//!By default, system clock is used.
+//!For tests it can be replaced with [Mock](struct.Mock.html) clock, which returns fixed or stepping time.
+//!
+//!```rust
+//!use core::time::Duration;
+//!
+//!static CLOCK: rogu::clock::Mock = rogu::clock::Mock::stepping(Duration::from_secs(1_600_000_000), Duration::from_secs(1));
+//!rogu::clock::set(&CLOCK);
+//!
+//!assert_eq!(rogu::clock::now(), Duration::from_secs(1_600_000_000));
+//!assert_eq!(rogu::clock::now(), Duration::from_secs(1_600_000_001));
+//!```
+
+use core::time::Duration;
+use core::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{PoisonError, RwLock};
+
+///Clock, returning time since UNIX epoch.
+pub trait Clock: Sync {
+    ///Returns current time.
+    fn now(&self) -> Duration;
+
+    #[inline]
+    ///Returns monotonic time, used to measure intervals (e.g. by rate limited macros).
+    ///
+    ///By default it is the same as `now`.
+    fn monotonic(&self) -> Duration {
+        self.now()
+    }
+}
+
+///System clock.
+pub struct System;
+
+impl Clock for System {
+    #[inline]
+    fn now(&self) -> Duration {
+        match std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH) {
+            Ok(time) => time,
+            Err(_) => Duration::from_secs(0),
+        }
+    }
+
+    #[inline]
+    ///Returns time since first call, which never goes backwards.
+    fn monotonic(&self) -> Duration {
+        static START: std::sync::OnceLock<std::time::Instant> = std::sync::OnceLock::new();
+        START.get_or_init(std::time::Instant::now).elapsed()
+    }
+}
+
+///Mock clock, returning fixed time, which is advanced by `step` on every call.
+pub struct Mock {
+    nanos: AtomicU64,
+    step: AtomicU64,
+}
+
+impl Mock {
+    #[inline]
+    ///Creates clock, that always returns `time`, unless changed.
+    pub const fn fixed(time: Duration) -> Self {
+        Self::stepping(time, Duration::from_secs(0))
+    }
+
+    #[inline]
+    ///Creates clock, that starts at `time` and advances by `step` after every call.
+    pub const fn stepping(time: Duration, step: Duration) -> Self {
+        Self {
+            nanos: AtomicU64::new(time.as_nanos() as u64),
+            step: AtomicU64::new(step.as_nanos() as u64),
+        }
+    }
+
+    #[inline]
+    ///Sets current time.
+    pub fn set(&self, time: Duration) {
+        self.nanos.store(time.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    #[inline]
+    ///Advances current time by `time`.
+    pub fn advance(&self, time: Duration) {
+        self.nanos.fetch_add(time.as_nanos() as u64, Ordering::Relaxed);
+    }
+}
+
+impl Clock for Mock {
+    #[inline]
+    fn now(&self) -> Duration {
+        Duration::from_nanos(self.nanos.fetch_add(self.step.load(Ordering::Relaxed), Ordering::Relaxed))
+    }
+}
+
+static CLOCK: RwLock<Option<&'static dyn Clock>> = RwLock::new(None);
+
+#[inline]
+fn get() -> Option<&'static dyn Clock> {
+    *CLOCK.read().unwrap_or_else(PoisonError::into_inner)
+}
+
+///Sets clock to be used by logger and sinks, replacing previous one.
+///
+///It can be called any number of times (e.g. by every test with its own mock clock).
+pub fn set(clock: &'static dyn Clock) {
+    *CLOCK.write().unwrap_or_else(PoisonError::into_inner) = Some(clock);
+}
+
+///Returns current time of the clock.
+pub fn now() -> Duration {
+    match get() {
+        Some(clock) => clock.now(),
+        None => System.now(),
+    }
+}
+
+///Returns monotonic time of the clock.
+pub fn monotonic() -> Duration {
+    match get() {
+        Some(clock) => clock.monotonic(),
+        None => System.monotonic(),
+    }
+}
@@ -0,0 +1,266 @@
+//!Scoped context fields.
+//!
+//![context!](../macro.context.html) pushes fields onto stack, from which they are removed once returned guard is dropped.
+//!While fields are on stack, every record of `core::fmt` based macros includes them before message:
+//!
+//!```text
+//!INFO  - [src/main.rs:10] - [request_id=42 user=bob] message
+//!```
+//!
+//!Sinks can read fields with [for_each](fn.for_each.html), as they are called on the thread, that writes record.
+//!
+//!With `std` stack is per thread.
+//!Otherwise it is single global stack of [MAX_FIELDS](constant.MAX_FIELDS.html) fields with values truncated to [MAX_VALUE_LEN](constant.MAX_VALUE_LEN.html) bytes,
+//!and fields, that do not fit or are pushed while stack is accessed by interrupted code, are dropped.
+//!
+//!```rust
+//!let request_id = 42;
+//!let _context = rogu::context!(request_id = request_id, user = "bob");
+//!rogu::info!("Written with request_id and user");
+//!```
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+mod stack {
+    use core::cell::{Cell, RefCell};
+    use core::cmp;
+    use core::fmt::Write;
+    use std::string::String;
+    use std::vec::Vec;
+
+    std::thread_local! {
+        static FIELDS: RefCell<Vec<(&'static str, String)>> = const { RefCell::new(Vec::new()) };
+        //Length, requested by `truncate` while fields were visited, applied once they can be modified.
+        static PENDING: Cell<usize> = const { Cell::new(usize::MAX) };
+    }
+
+    #[inline]
+    fn pending() -> usize {
+        PENDING.try_with(Cell::get).unwrap_or(usize::MAX)
+    }
+
+    #[inline]
+    pub fn len() -> Option<usize> {
+        FIELDS.try_with(|fields| fields.try_borrow().ok().map(|fields| cmp::min(fields.len(), pending()))).ok().flatten()
+    }
+
+    pub fn push(key: &'static str, value: core::fmt::Arguments<'_>) {
+        let mut text = String::new();
+        let _ = text.write_fmt(value);
+        //Field is dropped, if fields are visited, as no_std stack does when it is locked
+        let _ = FIELDS.try_with(|fields| if let Ok(mut fields) = fields.try_borrow_mut() {
+            fields.truncate(PENDING.with(|pending| pending.replace(usize::MAX)));
+            fields.push((key, text));
+        });
+    }
+
+    #[inline]
+    pub fn truncate(len: usize) {
+        let _ = FIELDS.try_with(|fields| match fields.try_borrow_mut() {
+            Ok(mut fields) => fields.truncate(cmp::min(len, PENDING.with(|pending| pending.replace(usize::MAX)))),
+            Err(_) => PENDING.with(|pending| pending.set(cmp::min(len, pending.get()))),
+        });
+    }
+
+    pub fn for_each<F: FnMut(&str, &str)>(mut fun: F) {
+        //Fields are skipped if record is written while they are modified
+        let _ = FIELDS.try_with(|fields| if let Ok(fields) = fields.try_borrow() {
+            let len = cmp::min(fields.len(), pending());
+            for (key, value) in fields[..len].iter() {
+                fun(key, value)
+            }
+        });
+    }
+}
+
+#[cfg(not(feature = "std"))]
+mod stack {
+    use super::{MAX_FIELDS, MAX_VALUE_LEN};
+
+    use core::{cmp, fmt};
+    use core::cell::UnsafeCell;
+    use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    #[derive(Clone, Copy)]
+    struct Field {
+        key: &'static str,
+        len: usize,
+        value: [u8; MAX_VALUE_LEN],
+    }
+
+    impl Field {
+        const EMPTY: Self = Self {
+            key: "",
+            len: 0,
+            value: [0; MAX_VALUE_LEN],
+        };
+    }
+
+    impl fmt::Write for Field {
+        fn write_str(&mut self, text: &str) -> fmt::Result {
+            let mut len = cmp::min(MAX_VALUE_LEN - self.len, text.len());
+            while !text.is_char_boundary(len) {
+                len -= 1;
+            }
+
+            self.value[self.len..self.len + len].copy_from_slice(&text.as_bytes()[..len]);
+            self.len += len;
+            Ok(())
+        }
+    }
+
+    struct Stack {
+        len: UnsafeCell<usize>,
+        fields: UnsafeCell<[Field; MAX_FIELDS]>,
+    }
+
+    //Accessed only under `LOCK`
+    unsafe impl Sync for Stack {}
+
+    static STACK: Stack = Stack {
+        len: UnsafeCell::new(0),
+        fields: UnsafeCell::new([Field::EMPTY; MAX_FIELDS]),
+    };
+    static LOCK: AtomicBool = AtomicBool::new(false);
+    //Length, requested by `truncate` while lock was held, applied by next holder.
+    static PENDING: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+    //Lock is never waited on, so that interrupted holder cannot be deadlocked.
+    fn with_lock<R, F: FnOnce(&mut usize, &mut [Field; MAX_FIELDS]) -> R>(fun: F) -> Option<R> {
+        if LOCK.compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed).is_err() {
+            return None;
+        }
+
+        let result = unsafe {
+            let len = &mut *STACK.len.get();
+            *len = cmp::min(*len, PENDING.swap(usize::MAX, Ordering::Acquire));
+            fun(len, &mut *STACK.fields.get())
+        };
+        LOCK.store(false, Ordering::Release);
+        Some(result)
+    }
+
+    #[inline]
+    pub fn len() -> Option<usize> {
+        with_lock(|len, _| *len)
+    }
+
+    pub fn push(key: &'static str, value: fmt::Arguments<'_>) {
+        let mut field = Field {
+            key,
+            ..Field::EMPTY
+        };
+        let _ = fmt::write(&mut field, value);
+
+        with_lock(|len, fields| if *len < MAX_FIELDS {
+            fields[*len] = field;
+            *len += 1;
+        });
+    }
+
+    #[inline]
+    pub fn truncate(new_len: usize) {
+        //If lock is taken, fields are removed before anyone else can read them.
+        PENDING.fetch_min(new_len, Ordering::Release);
+        with_lock(|_, _| ());
+    }
+
+    pub fn for_each<F: FnMut(&str, &str)>(mut fun: F) {
+        with_lock(|len, fields| for field in fields[..*len].iter() {
+            //Value is truncated on char boundary
+            fun(field.key, unsafe {
+                core::str::from_utf8_unchecked(&field.value[..field.len])
+            });
+        });
+    }
+}
+
+///Maximum number of fields without `std`.
+pub const MAX_FIELDS: usize = 16;
+///Maximum length of field's value without `std`.
+pub const MAX_VALUE_LEN: usize = 64;
+
+///Guard of context fields, removing them when dropped.
+///
+///Guards are expected to be dropped in reverse order of creation, as dropping guard removes fields of all guards created after it.
+pub struct Guard {
+    //Stack length before guard's fields, unknown if stack was busy.
+    len: Option<usize>,
+    //Fields are per thread
+    _not_send: core::marker::PhantomData<*const ()>,
+}
+
+impl Guard {
+    #[doc(hidden)]
+    #[inline]
+    pub fn new() -> Self {
+        Self {
+            len: stack::len(),
+            _not_send: core::marker::PhantomData,
+        }
+    }
+
+    #[doc(hidden)]
+    #[inline]
+    pub fn push(&self, key: &'static str, value: fmt::Arguments<'_>) {
+        //Without known length, fields could not be removed later
+        if self.len.is_some() {
+            stack::push(key, value);
+        }
+    }
+}
+
+impl Drop for Guard {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(len) = self.len {
+            stack::truncate(len);
+        }
+    }
+}
+
+///Calls `fun` with every field of current context, from outermost to innermost.
+pub fn for_each<F: FnMut(&str, &str)>(fun: F) {
+    stack::for_each(fun)
+}
+
+#[doc(hidden)]
+///Renders current context as `[key=value ...] `, if any.
+pub struct Prefix;
+
+impl fmt::Display for Prefix {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut result = Ok(());
+        let mut is_first = true;
+        for_each(|key, value| {
+            let separator = match is_first {
+                true => "[",
+                false => " ",
+            };
+            is_first = false;
+            if result.is_ok() {
+                result = write!(fmt, "{}{}={}", separator, key, value);
+            }
+        });
+
+        match is_first {
+            true => result,
+            false => result.and_then(|_| fmt.write_str("] ")),
+        }
+    }
+}
+
+#[macro_export]
+///Pushes fields onto context, returning guard, that removes them once dropped.
+///
+///Fields are specified as `key = value`, where value implements `Display`.
+macro_rules! context {
+    ($($key:ident = $value:expr),+ $(,)?) => {{
+        let guard = $crate::context::Guard::new();
+        $(
+            guard.push(core::stringify!($key), core::format_args!("{}", $value));
+        )+
+        guard
+    }}
+}
@@ -1,3 +1,160 @@
+#[cfg(not(any(feature = "log_facade", feature = "binary", feature = "deferred")))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_write {
+    ($level:ident, $out:ident, ) => {
+        $crate::__log_write!($level, $out, "")
+    };
+    ($level:ident, $out:ident, target: $target:expr, $($arg:tt)*) => {
+        match $target {
+            target => if $crate::is_target_enabled(target, $crate::Level::$level) {
+                match core::format_args!($($arg)*) {
+                    args => {
+                        use core::fmt::Write;
+                        if $crate::__is_platform_direct() {
+                            let _ = core::writeln!($crate::Out::$out(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") ), "{}{}: {}", $crate::context::Prefix, target, args);
+                        }
+                        $crate::sink::dispatch($crate::Level::$level, core::file!(), core::line!(), core::module_path!(), target, args);
+                    }
+                }
+            }
+        }
+    };
+    ($level:ident, $out:ident, $($arg:tt)*) => {
+        if $crate::is_enabled($crate::Level::$level) {
+            match core::format_args!($($arg)*) {
+                args => {
+                    use core::fmt::Write;
+                    if $crate::__is_platform_direct() {
+                        let _ = core::writeln!($crate::Out::$out(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") ), "{}{}", $crate::context::Prefix, args);
+                    }
+                    $crate::sink::dispatch($crate::Level::$level, core::file!(), core::line!(), core::module_path!(), core::module_path!(), args);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "deferred", not(any(feature = "log_facade", feature = "binary"))))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_write {
+    ($level:ident, $out:ident, ) => {
+        $crate::__log_write!($level, $out, "")
+    };
+    ($level:ident, $out:ident, target: $target:expr, $($arg:tt)*) => {
+        match $target {
+            target => if $crate::is_target_enabled(target, $crate::Level::$level) {
+                match core::format_args!($($arg)*) {
+                    args => {
+                        use core::fmt::Write;
+                        $crate::deferred::trigger($crate::Level::$level);
+                        if $crate::__is_platform_direct() {
+                            let _ = core::writeln!($crate::Out::$out(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") ), "{}{}: {}", $crate::context::Prefix, target, args);
+                        }
+                        $crate::sink::dispatch($crate::Level::$level, core::file!(), core::line!(), core::module_path!(), target, args);
+                    }
+                }
+            } else if $crate::deferred::is_deferred($crate::Level::$level) {
+                $crate::deferred::defer($crate::Level::$level, core::concat!("- [", core::file!(), ":", core::line!(), "] - "), core::file!(), core::line!(), core::module_path!(), Some(target), core::format_args!($($arg)*));
+            }
+        }
+    };
+    ($level:ident, $out:ident, $($arg:tt)*) => {
+        if $crate::is_enabled($crate::Level::$level) {
+            match core::format_args!($($arg)*) {
+                args => {
+                    use core::fmt::Write;
+                    $crate::deferred::trigger($crate::Level::$level);
+                    if $crate::__is_platform_direct() {
+                        let _ = core::writeln!($crate::Out::$out(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") ), "{}{}", $crate::context::Prefix, args);
+                    }
+                    $crate::sink::dispatch($crate::Level::$level, core::file!(), core::line!(), core::module_path!(), core::module_path!(), args);
+                }
+            }
+        } else if $crate::deferred::is_deferred($crate::Level::$level) {
+            $crate::deferred::defer($crate::Level::$level, core::concat!("- [", core::file!(), ":", core::line!(), "] - "), core::file!(), core::line!(), core::module_path!(), None, core::format_args!($($arg)*));
+        }
+    }
+}
+
+#[cfg(feature = "binary")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_write {
+    ($level:ident, $out:ident, ) => {
+        $crate::__log_write!($level, $out, "")
+    };
+    ($level:ident, $out:ident, target: $target:literal, $fmt:literal $(, $arg:expr)* $(,)?) => {
+        if $crate::is_target_enabled($target, $crate::Level::$level) {
+            const FMT: &str = core::concat!(core::stringify!($level), "\x1f", core::file!(), "\x1f", core::line!(), "\x1f", $target, ": ", $fmt, "\0");
+            #[link_section = "rogu_fmt"]
+            static INTERNED: [u8; FMT.len()] = $crate::binary::intern(FMT);
+            let mut frame = $crate::binary::Frame::new(&INTERNED);
+            $( $crate::binary::Encode::encode(&$arg, &mut frame); )*
+            frame.finish();
+        }
+    };
+    ($level:ident, $out:ident, $fmt:literal $(, $arg:expr)* $(,)?) => {
+        if $crate::is_enabled($crate::Level::$level) {
+            const FMT: &str = core::concat!(core::stringify!($level), "\x1f", core::file!(), "\x1f", core::line!(), "\x1f", $fmt, "\0");
+            #[link_section = "rogu_fmt"]
+            static INTERNED: [u8; FMT.len()] = $crate::binary::intern(FMT);
+            let mut frame = $crate::binary::Frame::new(&INTERNED);
+            $( $crate::binary::Encode::encode(&$arg, &mut frame); )*
+            frame.finish();
+        }
+    }
+}
+
+#[cfg(feature = "log_facade")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_write {
+    ($level:ident, $out:ident, ) => {
+        $crate::__log_write!($level, $out, "")
+    };
+    ($level:ident, $out:ident, target: $target:expr, $($arg:tt)*) => {
+        if $crate::__log_enabled($crate::Level::$level) {
+            $crate::__log_forward($crate::Level::$level, core::file!(), core::line!(), core::module_path!(), $target, core::format_args!("{}{}", $crate::context::Prefix, core::format_args!($($arg)*)));
+        }
+    };
+    ($level:ident, $out:ident, $($arg:tt)*) => {
+        if $crate::__log_enabled($crate::Level::$level) {
+            $crate::__log_forward($crate::Level::$level, core::file!(), core::line!(), core::module_path!(), core::module_path!(), core::format_args!("{}{}", $crate::context::Prefix, core::format_args!($($arg)*)));
+        }
+    }
+}
+
+#[macro_export]
+///Writes fatal log, flushes sinks and terminates program.
+///
+///Fatal log is written regardless of level and compile time features.
+///Program is terminated by handler set with [set_fatal_handler](fn.set_fatal_handler.html), or by abort.
+macro_rules! fatal {
+    ($($arg:tt)*) => {{
+        $crate::__log_write!(FATAL, fatal, $($arg)*);
+        $crate::__fatal_terminate()
+    }}
+}
+
+#[cfg(all(feature = "extended_levels", any(all(debug_assertions, feature = "level_critical_off"), all(not(debug_assertions), feature = "release_level_critical_off") )))]
+#[macro_export]
+///Writes critical log
+macro_rules! critical {
+    ($($arg:tt)*) => {
+    }
+}
+
+#[cfg(all(feature = "extended_levels", any(all(debug_assertions, not(feature = "level_critical_off")), all(not(debug_assertions), not(feature = "release_level_critical_off")) )))]
+#[macro_export]
+///Writes critical log
+macro_rules! critical {
+    ($($arg:tt)*) => {
+        $crate::__log_write!(CRITICAL, critical, $($arg)*)
+    }
+}
+
 #[cfg(any(all(debug_assertions, feature = "level_error_off"), all(not(debug_assertions), feature = "release_level_error_off") ) )]
 #[macro_export]
 ///Writes error log
@@ -11,10 +168,7 @@ macro_rules! error {
 ///Writes error log
 macro_rules! error {
     ($($arg:tt)*) => {
-        if $crate::is_enabled($crate::Level::ERROR) {
-            use core::fmt::Write;
-            let _ = core::writeln!($crate::Out::error(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") ), $($arg)*);
-        }
+        $crate::__log_write!(ERROR, error, $($arg)*)
     }
 }
 
@@ -31,10 +185,24 @@ macro_rules! warn {
 ///Writes warn log
 macro_rules! warn {
     ($($arg:tt)*) => {
-        if $crate::is_enabled($crate::Level::WARN) {
-            use core::fmt::Write;
-            let _ = core::writeln!($crate::Out::warn(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") ), $($arg)*);
-        }
+        $crate::__log_write!(WARN, warn, $($arg)*)
+    }
+}
+
+#[cfg(all(feature = "extended_levels", any(all(debug_assertions, feature = "level_notice_off"), all(not(debug_assertions), feature = "release_level_notice_off") )))]
+#[macro_export]
+///Writes notice log
+macro_rules! notice {
+    ($($arg:tt)*) => {
+    }
+}
+
+#[cfg(all(feature = "extended_levels", any(all(debug_assertions, not(feature = "level_notice_off")), all(not(debug_assertions), not(feature = "release_level_notice_off")) )))]
+#[macro_export]
+///Writes notice log
+macro_rules! notice {
+    ($($arg:tt)*) => {
+        $crate::__log_write!(NOTICE, notice, $($arg)*)
     }
 }
 
@@ -51,10 +219,7 @@ macro_rules! info {
 ///Writes info log
 macro_rules! info {
     ($($arg:tt)*) => {
-        if $crate::is_enabled($crate::Level::INFO) {
-            use core::fmt::Write;
-            let _ = core::writeln!($crate::Out::info(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") ), $($arg)*);
-        }
+        $crate::__log_write!(INFO, info, $($arg)*)
     }
 }
 
@@ -71,10 +236,7 @@ macro_rules! debug {
 ///Writes debug log
 macro_rules! debug {
     ($($arg:tt)*) => {
-        if $crate::is_enabled($crate::Level::DEBUG) {
-            use core::fmt::Write;
-            let _ = core::writeln!($crate::Out::debug(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") ), $($arg)*);
-        }
+        $crate::__log_write!(DEBUG, debug, $($arg)*)
     }
 }
 
@@ -91,9 +253,6 @@ macro_rules! trace {
 ///Writes trace log
 macro_rules! trace {
     ($($arg:tt)*) => {
-        if $crate::is_enabled($crate::Level::TRACE) {
-            use core::fmt::Write;
-            let _ = core::writeln!($crate::Out::trace(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") ), $($arg)*);
-        }
+        $crate::__log_write!(TRACE, trace, $($arg)*)
     }
 }
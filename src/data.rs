@@ -1,7 +1,29 @@
 pub mod level {
+    pub const FATAL: &str = "FATAL ";
+    #[cfg(feature = "extended_levels")]
+    pub const CRITICAL: &str = "CRITICAL ";
     pub const ERROR: &str = "ERROR ";
     pub const WARN: &str = "WARN  ";
+    #[cfg(feature = "extended_levels")]
+    pub const NOTICE: &str = "NOTICE ";
     pub const INFO: &str = "INFO  ";
     pub const DEBUG: &str = "DEBUG ";
     pub const TRACE: &str = "TRACE ";
+
+    #[allow(unused)]
+    pub const fn name(level: crate::Level) -> &'static str {
+        match level {
+            crate::Level::NONE => "NONE",
+            crate::Level::FATAL => "FATAL",
+            #[cfg(feature = "extended_levels")]
+            crate::Level::CRITICAL => "CRITICAL",
+            crate::Level::ERROR => "ERROR",
+            crate::Level::WARN => "WARN",
+            #[cfg(feature = "extended_levels")]
+            crate::Level::NOTICE => "NOTICE",
+            crate::Level::INFO => "INFO",
+            crate::Level::DEBUG => "DEBUG",
+            crate::Level::TRACE => "TRACE",
+        }
+    }
 }
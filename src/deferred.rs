@@ -0,0 +1,141 @@
+//!Deferred verbose records.
+//!
+//!Records, that are disabled by [set_level](../fn.set_level.html), but enabled by deferred [set_level](fn.set_level.html),
+//!are not written immediately.
+//!Instead they are kept in bounded per thread buffer, which is written out right before next `error!` on the same thread,
+//!giving context of the error.
+//!
+//!Otherwise deferred records are discarded, once buffer is full or [clear](fn.clear.html) is called (e.g. at the end of request, using [scope](fn.scope.html)).
+//!
+//!Deferred records are written with time of the flush, not of the original call.
+//!
+//!```rust
+//!rogu::set_level(rogu::Level::WARN);
+//!rogu::deferred::set_level(rogu::Level::DEBUG);
+//!
+//!let _scope = rogu::deferred::scope();
+//!rogu::debug!("Kept until error");
+//!rogu::error!("Written after 'Kept until error'");
+//!```
+
+use crate::Level;
+
+use core::cell::RefCell;
+use core::fmt::{self, Write};
+use core::sync::atomic::{AtomicU8, Ordering};
+use std::collections::VecDeque;
+use std::string::{String, ToString};
+
+///Maximum number of deferred records per thread.
+///
+///Once reached, oldest records are discarded.
+pub const MAX_RECORDS: usize = 64;
+
+static LEVEL: AtomicU8 = AtomicU8::new(0);
+
+struct Deferred {
+    level: Level,
+    location: &'static str,
+    file: &'static str,
+    line: u32,
+    module: &'static str,
+    target: Option<String>,
+    context: String,
+    message: String,
+}
+
+std::thread_local! {
+    static RECORDS: RefCell<VecDeque<Deferred>> = const { RefCell::new(VecDeque::new()) };
+}
+
+///Sets maximum level of deferred records.
+///
+///By default, nothing is deferred.
+pub fn set_level<L: Into<crate::LevelFilter>>(level: L) {
+    LEVEL.store(level.into() as u8, Ordering::Relaxed);
+}
+
+#[inline]
+///Checks if record with `level` is to be deferred.
+pub fn is_deferred(level: Level) -> bool {
+    crate::is_static_enabled(level) && !crate::is_enabled(level) && LEVEL.load(Ordering::Relaxed) >= level as u8
+}
+
+#[doc(hidden)]
+///Stores record in current thread's buffer.
+pub fn defer(level: Level, location: &'static str, file: &'static str, line: u32, module: &'static str, target: Option<&str>, args: fmt::Arguments<'_>) {
+    let record = Deferred {
+        level,
+        location,
+        file,
+        line,
+        module,
+        target: target.map(ToString::to_string),
+        context: crate::context::Prefix.to_string(),
+        message: args.to_string(),
+    };
+
+    let _ = RECORDS.try_with(|records| {
+        let mut records = records.borrow_mut();
+        if records.len() == MAX_RECORDS {
+            records.pop_front();
+        }
+        records.push_back(record);
+    });
+}
+
+#[doc(hidden)]
+#[inline]
+///Flushes deferred records, if `level` is error or more severe.
+pub fn trigger(level: Level) {
+    if level != Level::NONE && level as u8 <= Level::ERROR as u8 {
+        flush();
+    }
+}
+
+///Writes current thread's deferred records.
+pub fn flush() {
+    let records = match RECORDS.try_with(|records| core::mem::take(&mut *records.borrow_mut())) {
+        Ok(records) => records,
+        Err(_) => return,
+    };
+
+    for record in records {
+        if crate::out::is_direct() {
+            let mut out = crate::out::by_level(record.level, record.location);
+            let _ = match record.target.as_deref() {
+                Some(target) => writeln!(out, "{}{}: {}", record.context, target, record.message),
+                None => writeln!(out, "{}{}", record.context, record.message),
+            };
+        }
+        let target = record.target.as_deref().unwrap_or(record.module);
+        crate::sink::dispatch(record.level, record.file, record.line, record.module, target, format_args!("{}", record.message));
+    }
+}
+
+///Discards current thread's deferred records.
+pub fn clear() {
+    let _ = RECORDS.try_with(|records| records.borrow_mut().clear());
+}
+
+///Scope of deferred records, discarding them on creation and drop.
+pub struct Scope {
+    //Records are per thread
+    _not_send: core::marker::PhantomData<*const ()>,
+}
+
+impl Drop for Scope {
+    #[inline]
+    fn drop(&mut self) {
+        clear();
+    }
+}
+
+#[inline]
+///Starts new scope of deferred records (e.g. for single request).
+pub fn scope() -> Scope {
+    clear();
+    Scope {
+        _not_send: core::marker::PhantomData,
+    }
+}
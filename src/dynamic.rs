@@ -0,0 +1,107 @@
+//!Macros with level chosen at runtime.
+
+use crate::Level;
+
+macro_rules! is_compiled {
+    ($off:literal, $release_off:literal) => {
+        cfg!(any(all(debug_assertions, not(feature = $off)), all(not(debug_assertions), not(feature = $release_off))))
+    }
+}
+
+//Mirrors `cfg` of level macros.
+const fn is_compiled(level: Level) -> bool {
+    match level {
+        Level::NONE => false,
+        Level::FATAL => true,
+        #[cfg(feature = "extended_levels")]
+        Level::CRITICAL => is_compiled!("level_critical_off", "release_level_critical_off"),
+        Level::ERROR => is_compiled!("level_error_off", "release_level_error_off"),
+        Level::WARN => is_compiled!("level_warn_off", "release_level_warn_off"),
+        #[cfg(feature = "extended_levels")]
+        Level::NOTICE => is_compiled!("level_notice_off", "release_level_notice_off"),
+        Level::INFO => is_compiled!("level_info_off", "release_level_info_off"),
+        Level::DEBUG => is_compiled!("level_debug_off", "release_level_debug_off"),
+        Level::TRACE => is_compiled!("level_trace_off", "release_level_trace_off"),
+    }
+}
+
+#[inline]
+///Checks if macro of `level` is compiled and enabled.
+pub fn enabled(level: Level) -> bool {
+    #[cfg(feature = "log_facade")]
+    {
+        is_compiled(level) && crate::rust_log::enabled(level)
+    }
+    #[cfg(not(feature = "log_facade"))]
+    {
+        is_compiled(level) && crate::is_enabled(level)
+    }
+}
+
+#[inline]
+///Checks if macro of `level` with explicit `target` is compiled and enabled.
+pub(crate) fn target_enabled(target: &str, level: Level) -> bool {
+    #[cfg(feature = "log_facade")]
+    {
+        let _ = target;
+        is_compiled(level) && crate::rust_log::enabled(level)
+    }
+    #[cfg(not(feature = "log_facade"))]
+    {
+        is_compiled(level) && crate::is_target_enabled(target, level)
+    }
+}
+
+#[cfg(not(feature = "extended_levels"))]
+#[macro_export]
+///Writes log with level, chosen at runtime
+///
+///Record is written by macro of corresponding level, so that it is subject to the same checks.
+///`Level::FATAL` terminates program as `fatal!` does.
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        match $level {
+            $crate::Level::FATAL => { $crate::fatal!($($arg)*); },
+            $crate::Level::ERROR => { $crate::error!($($arg)*); },
+            $crate::Level::WARN => { $crate::warn!($($arg)*); },
+            $crate::Level::INFO => { $crate::info!($($arg)*); },
+            $crate::Level::DEBUG => { $crate::debug!($($arg)*); },
+            $crate::Level::TRACE => { $crate::trace!($($arg)*); },
+            //`Level::NONE`
+            _ => (),
+        }
+    }
+}
+
+#[cfg(feature = "extended_levels")]
+#[macro_export]
+///Writes log with level, chosen at runtime
+///
+///Record is written by macro of corresponding level, so that it is subject to the same checks.
+///`Level::FATAL` terminates program as `fatal!` does.
+macro_rules! log {
+    ($level:expr, $($arg:tt)*) => {
+        match $level {
+            $crate::Level::FATAL => { $crate::fatal!($($arg)*); },
+            $crate::Level::CRITICAL => { $crate::critical!($($arg)*); },
+            $crate::Level::ERROR => { $crate::error!($($arg)*); },
+            $crate::Level::WARN => { $crate::warn!($($arg)*); },
+            $crate::Level::NOTICE => { $crate::notice!($($arg)*); },
+            $crate::Level::INFO => { $crate::info!($($arg)*); },
+            $crate::Level::DEBUG => { $crate::debug!($($arg)*); },
+            $crate::Level::TRACE => { $crate::trace!($($arg)*); },
+            //`Level::NONE`
+            _ => (),
+        }
+    }
+}
+
+#[macro_export]
+///Checks if logging is enabled for level, chosen at runtime
+///
+///Unlike [is_enabled](fn.is_enabled.html), it also takes into account levels disabled by features.
+macro_rules! enabled {
+    ($level:expr) => {
+        $crate::__enabled($level)
+    }
+}
@@ -0,0 +1,34 @@
+use core::{mem, ptr};
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+static HANDLER: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+///Sets handler, called by `fatal!` after record is written and sinks are flushed.
+///
+///If handler returns, program is still terminated.
+pub fn set_fatal_handler(handler: fn()) {
+    HANDLER.store(handler as *mut (), Ordering::Release);
+}
+
+#[cold]
+#[inline(never)]
+///Flushes sinks and terminates program.
+///
+///With `std` program is aborted, otherwise it panics.
+pub fn terminate() -> ! {
+    #[cfg(not(feature = "ufmt"))]
+    crate::sink::flush();
+
+    let handler = HANDLER.load(Ordering::Acquire);
+    if !handler.is_null() {
+        let handler: fn() = unsafe {
+            mem::transmute::<*mut (), fn()>(handler)
+        };
+        handler();
+    }
+
+    #[cfg(feature = "std")]
+    std::process::abort();
+    #[cfg(not(feature = "std"))]
+    panic!("fatal error");
+}
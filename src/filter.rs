@@ -0,0 +1,107 @@
+//!Per-target filtering
+
+use crate::Level;
+
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+
+///Maximum number of targets with own level.
+pub const MAX_TARGETS: usize = 16;
+
+struct Targets {
+    names: UnsafeCell<[&'static str; MAX_TARGETS]>,
+    levels: [AtomicU8; MAX_TARGETS],
+}
+
+//Names are written only once under `LOCK` before `LEN` is published
+unsafe impl Sync for Targets {}
+
+#[allow(clippy::declare_interior_mutable_const)]
+const LEVEL_INIT: AtomicU8 = AtomicU8::new(0);
+static TARGETS: Targets = Targets {
+    names: UnsafeCell::new([""; MAX_TARGETS]),
+    levels: [LEVEL_INIT; MAX_TARGETS],
+};
+static LEN: AtomicUsize = AtomicUsize::new(0);
+static LOCK: AtomicBool = AtomicBool::new(false);
+
+#[inline]
+fn names() -> &'static [&'static str] {
+    let len = LEN.load(Ordering::Acquire);
+    //Names beyond `len` may be written concurrently, so no reference to whole array is created
+    unsafe {
+        core::slice::from_raw_parts(TARGETS.names.get() as *const &'static str, len)
+    }
+}
+
+#[inline]
+fn is_match(prefix: &str, target: &str) -> bool {
+    match target.strip_prefix(prefix) {
+        Some(rest) => rest.is_empty() || rest.starts_with("::"),
+        None => false,
+    }
+}
+
+///Sets logging level for `target` and all its sub-modules, overriding global level.
+///
+///Returns `false` if there are already `MAX_TARGETS` targets.
+pub fn set_target_level<L: Into<crate::LevelFilter>>(target: &'static str, level: L) -> bool {
+    let level = level.into();
+
+    while LOCK.compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed).is_err() {
+        core::hint::spin_loop();
+    }
+
+    let len = LEN.load(Ordering::Relaxed);
+    let result = match names().iter().position(|name| *name == target) {
+        Some(idx) => {
+            TARGETS.levels[idx].store(level as u8, Ordering::Relaxed);
+            true
+        },
+        None if len < MAX_TARGETS => {
+            unsafe {
+                (TARGETS.names.get() as *mut &'static str).add(len).write(target);
+            }
+            TARGETS.levels[len].store(level as u8, Ordering::Relaxed);
+            LEN.store(len + 1, Ordering::Release);
+            true
+        },
+        None => false,
+    };
+
+    LOCK.store(false, Ordering::Release);
+    crate::on_level_change();
+    result
+}
+
+#[cfg(feature = "log")]
+#[inline]
+pub(crate) fn max_level() -> u8 {
+    (0..LEN.load(Ordering::Acquire)).map(|idx| TARGETS.levels[idx].load(Ordering::Relaxed)).max().unwrap_or(0)
+}
+
+///Checks if logging is enabled for `target`
+///
+///Uses level of the most specific target set by `set_target_level`, falling back to global level.
+///
+///`FATAL` is always enabled.
+pub fn is_target_enabled(target: &str, level: Level) -> bool {
+    if level == Level::FATAL {
+        return true;
+    }
+
+    let mut matched = None;
+    for (idx, name) in names().iter().enumerate() {
+        if is_match(name, target) {
+            match matched {
+                Some((_, len)) if len >= name.len() => (),
+                _ => matched = Some((idx, name.len())),
+            }
+        }
+    }
+
+    match matched {
+        Some((idx, _)) => crate::is_static_enabled(level) && TARGETS.levels[idx].load(Ordering::Relaxed) >= level as u8,
+        None => crate::is_enabled(level),
+    }
+}
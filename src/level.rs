@@ -0,0 +1,273 @@
+//!Level parsing, ordering and formatting.
+
+use crate::{data, Level};
+
+use core::{cmp, fmt, str};
+
+///Maximum level of records to write.
+///
+///Unlike [Level](enum.Level.html) it can be `OFF`, in which case only `fatal!` records are written.
+#[repr(u8)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum LevelFilter {
+    ///Disables all records, except fatal.
+    OFF = 0,
+    ///Enables fatal records.
+    FATAL = 1,
+    #[cfg(feature = "extended_levels")]
+    ///Enables critical and more severe records.
+    CRITICAL = 2,
+    ///Enables error and more severe records.
+    ERROR = 3,
+    ///Enables warn and more severe records.
+    WARN = 4,
+    #[cfg(feature = "extended_levels")]
+    ///Enables notice and more severe records.
+    NOTICE = 5,
+    ///Enables info and more severe records.
+    INFO = 6,
+    ///Enables debug and more severe records.
+    DEBUG = 7,
+    ///Enables all records.
+    TRACE = 8,
+}
+
+impl LevelFilter {
+    pub(crate) const fn from_raw(level: u8) -> Self {
+        match level {
+            0 => LevelFilter::OFF,
+            1 => LevelFilter::FATAL,
+            #[cfg(feature = "extended_levels")]
+            2 => LevelFilter::CRITICAL,
+            3 => LevelFilter::ERROR,
+            4 => LevelFilter::WARN,
+            #[cfg(feature = "extended_levels")]
+            5 => LevelFilter::NOTICE,
+            6 => LevelFilter::INFO,
+            7 => LevelFilter::DEBUG,
+            _ => LevelFilter::TRACE,
+        }
+    }
+
+    #[inline]
+    ///Returns most verbose level, enabled by filter, if any.
+    pub const fn to_level(self) -> Option<Level> {
+        match self {
+            LevelFilter::OFF => None,
+            level => Some(Level::from_raw(level as u8)),
+        }
+    }
+}
+
+impl From<Level> for LevelFilter {
+    #[inline(always)]
+    fn from(level: Level) -> Self {
+        Self::from_raw(level as u8)
+    }
+}
+
+impl PartialOrd for Level {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Level {
+    #[inline(always)]
+    fn cmp(&self, other: &Self) -> cmp::Ordering {
+        (*self as u8).cmp(&(*other as u8))
+    }
+}
+
+impl PartialEq<LevelFilter> for Level {
+    #[inline(always)]
+    fn eq(&self, other: &LevelFilter) -> bool {
+        *self as u8 == *other as u8
+    }
+}
+
+impl PartialOrd<LevelFilter> for Level {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &LevelFilter) -> Option<cmp::Ordering> {
+        Some((*self as u8).cmp(&(*other as u8)))
+    }
+}
+
+impl PartialEq<Level> for LevelFilter {
+    #[inline(always)]
+    fn eq(&self, other: &Level) -> bool {
+        *self as u8 == *other as u8
+    }
+}
+
+impl PartialOrd<Level> for LevelFilter {
+    #[inline(always)]
+    fn partial_cmp(&self, other: &Level) -> Option<cmp::Ordering> {
+        Some((*self as u8).cmp(&(*other as u8)))
+    }
+}
+
+impl fmt::Display for Level {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.pad(data::level::name(*self))
+    }
+}
+
+impl fmt::Display for LevelFilter {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_level() {
+            Some(level) => fmt.pad(data::level::name(level)),
+            None => fmt.pad("OFF"),
+        }
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for Level {
+    #[inline]
+    fn fmt<W: ufmt::uWrite + ?Sized>(&self, fmt: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error> {
+        fmt.write_str(data::level::name(*self))
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uDisplay for LevelFilter {
+    #[inline]
+    fn fmt<W: ufmt::uWrite + ?Sized>(&self, fmt: &mut ufmt::Formatter<'_, W>) -> Result<(), W::Error> {
+        match self.to_level() {
+            Some(level) => fmt.write_str(data::level::name(level)),
+            None => fmt.write_str("OFF"),
+        }
+    }
+}
+
+const NAMES: &[(&str, LevelFilter)] = &[
+    ("off", LevelFilter::OFF),
+    ("none", LevelFilter::OFF),
+    ("fatal", LevelFilter::FATAL),
+    #[cfg(feature = "extended_levels")]
+    ("critical", LevelFilter::CRITICAL),
+    ("error", LevelFilter::ERROR),
+    ("warn", LevelFilter::WARN),
+    ("warning", LevelFilter::WARN),
+    #[cfg(feature = "extended_levels")]
+    ("notice", LevelFilter::NOTICE),
+    ("info", LevelFilter::INFO),
+    ("debug", LevelFilter::DEBUG),
+    ("trace", LevelFilter::TRACE),
+];
+
+#[inline]
+fn from_number(number: u64) -> Option<LevelFilter> {
+    NAMES.iter().map(|(_, level)| *level).find(|level| *level as u64 == number)
+}
+
+//Parses case-insensitive name or number.
+fn parse(text: &str) -> Option<LevelFilter> {
+    match text.parse::<u64>() {
+        Ok(number) => from_number(number),
+        Err(_) => NAMES.iter().find(|(name, _)| name.eq_ignore_ascii_case(text)).map(|(_, level)| *level),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+///Error of level parsing.
+pub struct ParseLevelError(());
+
+impl fmt::Display for ParseLevelError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt.write_str("invalid log level")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for ParseLevelError {
+}
+
+impl str::FromStr for Level {
+    type Err = ParseLevelError;
+
+    ///Parses case-insensitive level name (e.g. `warn` or `WARNING`) or its numeric value (e.g. `4`).
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse(text).and_then(LevelFilter::to_level).ok_or(ParseLevelError(()))
+    }
+}
+
+impl str::FromStr for LevelFilter {
+    type Err = ParseLevelError;
+
+    ///Parses case-insensitive level name (e.g. `warn`, `off`) or its numeric value (e.g. `4`, `0`).
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse(text).ok_or(ParseLevelError(()))
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use super::{data, from_number, parse, Level, LevelFilter};
+
+    use core::fmt;
+    use serde::de::{self, Unexpected};
+
+    impl serde::Serialize for Level {
+        #[inline]
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(data::level::name(*self))
+        }
+    }
+
+    impl serde::Serialize for LevelFilter {
+        #[inline]
+        fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            match self.to_level() {
+                Some(level) => serializer.serialize_str(data::level::name(level)),
+                None => serializer.serialize_str("OFF"),
+            }
+        }
+    }
+
+    struct Visitor;
+
+    impl de::Visitor<'_> for Visitor {
+        type Value = LevelFilter;
+
+        fn expecting(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt.write_str("log level name or number")
+        }
+
+        fn visit_str<E: de::Error>(self, text: &str) -> Result<Self::Value, E> {
+            parse(text).ok_or_else(|| E::invalid_value(Unexpected::Str(text), &self))
+        }
+
+        fn visit_u64<E: de::Error>(self, number: u64) -> Result<Self::Value, E> {
+            from_number(number).ok_or_else(|| E::invalid_value(Unexpected::Unsigned(number), &self))
+        }
+
+        fn visit_i64<E: de::Error>(self, number: i64) -> Result<Self::Value, E> {
+            match number {
+                0.. => self.visit_u64(number as u64),
+                _ => Err(E::invalid_value(Unexpected::Signed(number), &self)),
+            }
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for LevelFilter {
+        #[inline]
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_any(Visitor)
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for Level {
+        fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            match LevelFilter::deserialize(deserializer)?.to_level() {
+                Some(level) => Ok(level),
+                None => Err(de::Error::invalid_value(Unexpected::Str("OFF"), &"log level other than OFF")),
+            }
+        }
+    }
+}
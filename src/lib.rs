@@ -7,6 +7,7 @@
 //!- Web - Uses console API;
 //!- Android Log;
 //!- C stdio - writes to stdout/stderr;
+//!- Bare metal (`target_os = "none"`) - writes to user hook set by `set_write_hook`;
 //!
 //!## Features
 //!
@@ -14,42 +15,173 @@
 //!
 //!#### Loggers
 //!
-//!- `ufmt` - Enables [ufmt](https://github.com/japaric/ufmt) traits instead of core's `fmt`
-//!- `std` - Enables `std` usage, adding ability to use timestamps (not used in Web and Android)
-//!- `log` - Enables `log` usage, adding `log` logs to the output.
+//!- `ufmt` - Enables [ufmt](https://github.com/japaric/ufmt) traits instead of core's `fmt`. Callers do not need to depend on `ufmt` themselves.
+//!- `std` - Enables `std` usage, adding ability to use timestamps (not used in Web and Android) and to write C stdio output through `print!`,
+//!  so that it is captured by test harness, once enabled by [set_print(true)](fn.set_print.html)
+//!- `hook` - Uses bare metal logger, writing to user hook, on any target.
+//!- `binary` - Makes macros to write compact [binary](binary/index.html) frames into write hook, deferring formatting to host. Implies `hook`.
+//!- `decoder` - Enables host side [decoder](binary/decode/index.html) of binary frames and `rogu-decode` binary. Implies `std`.
+//!- `log` - Enables `log` usage, adding `log` logs to the output with target, module and key-values. Records are filtered by [is_target_enabled](fn.is_target_enabled.html).
+//!- `log_facade` - Makes macros to emit records into installed `log` logger instead of platform logger, allowing library to use rogu without choosing backend for binary. `set_level` sets max level of `log` only if no other logger is installed. Levels without `log` equivalent are forwarded with `level` key-value (e.g. `FATAL` as `Error` with `level="FATAL"`), so installed logger can filter them only as their `log` level.
+//!  If no other logger is installed, [set_level](fn.set_level.html) installs rogu's own, which writes to platform logger. Implies `log`.
+//!- `http` - Enables batched HTTP push [sink](sink/http/index.html) (Loki and OTLP JSON). Implies `std`.
+//!- `sqlite` - Enables [SQLite sink](sink/sqlite/index.html) with batched transactions and row based retention. Implies `std`.
+//!- `deferred` - Enables [deferred](deferred/index.html) verbose records, kept per thread and written only before `error!`. Not used by `log_facade` and `binary` macros.
+//!- `ring` - Enables in-memory [ring buffer](ring/index.html) of recent records, registered as sink by [set_level](fn.set_level.html). Not available with `ufmt`. Its size is set by `ROGU_RING_SLOTS` and `ROGU_RING_SLOT_SIZE` environment variables, longer records are truncated.
+//!- `testing` - Enables [capture](testing/index.html) of records and `assert_logged!` for tests. Not available with `ufmt` and `binary`.
+//!- `tracing` - Enables [tracing](tracing/index.html) layer, writing `tracing` events through platform logger.
+//!- `extended_levels` - Adds syslog severities `CRITICAL` (between `FATAL` and `ERROR`) and `NOTICE` (between `WARN` and `INFO`) with `critical!` and `notice!` macros. In `log` they are mapped to `Error` and `Info`.
+//!- `serde` - Implements `Serialize` and `Deserialize` for [Level](enum.Level.html) and [LevelFilter](enum.LevelFilter.html), using level names (numbers are accepted too).
+//!
+//!#### Sinks
+//!
+//!Besides platform logger, records can be passed to additional [sinks](sink/index.html).
+//!Sinks are not available with `ufmt`.
+//!With `std`, sink can be wrapped by [collapse](sink/collapse/index.html) adapter, replacing consecutive duplicate records with `last message repeated N times`.
+//!Platform logger can be collapsed too, by replacing it with [Platform](sink/collapse/struct.Platform.html) sink via [set_platform](sink/collapse/fn.set_platform.html).
+//!
+//!#### Reproducible output
+//!
+//!For snapshot tests, [set_deterministic](fn.set_deterministic.html) omits timestamps and strips directories from source file paths.
+//!With `std`, time source can be replaced by mock [clock](clock/index.html) instead.
+//!
+//!#### Targets
+//!
+//!Macros accept logical channel of record as `info!(target: "audit", ...)`, which is written before message and passed to sinks.
+//!Such records are filtered by level of their target, set by [set_target_level](fn.set_target_level.html). Target must be literal with `binary`.
+//!
+//!#### Runtime level
+//!
+//!`log!(level, ...)` writes record with level chosen at runtime, using macro of that level, and `enabled!(level)` checks it
+//!taking into account levels disabled at compile time.
+//!
+//!#### Context
+//!
+//!`let _guard = context!(request_id = id, user = name);` adds fields to every record of current thread until guard is dropped.
+//!They are written as `[request_id=1 user=bob]` before message and are available to sinks via [context::for_each](context/fn.for_each.html).
+//!Without `std` fields are kept in fixed-capacity stack, shared by whole program. Not used by `ufmt` and `binary` macros.
+//!
+//!#### Rate limiting
+//!
+//!Every level has rate limited variants of its macro, which keep state per callsite:
+//!
+//!- `warn_once!(...)` - Writes only first call;
+//!- `info_every_n!(n, ...)` - Writes first and then every `n`-th call;
+//!- `error_every!(Duration, ...)` - Writes at most once per interval. Requires `std` and uses monotonic time of [clock](clock/index.html);
+//!
+//!Once next call is written, it is followed by record with number of suppressed calls.
+//!Calls, that are disabled by level, are not counted.
+//!
+//!#### Buffer size
+//!
+//!Records are formatted into stack buffer, which is written out when full or when record ends.
+//!Record, that does not fit, is written as several lines, split after newline or whitespace if possible, with continuation lines prefixed by `... `.
+//!By default it is 4096 bytes (512 bytes for hook logger, 4000 bytes limit for Android), which can be changed with one of following features:
+//!
+//!- `buffer_256` - Uses 256 bytes buffer;
+//!- `buffer_1024` - Uses 1024 bytes buffer;
+//!- `buffer_16384` - Uses 16384 bytes buffer (Android is still limited to 4000);
+//!
+//!If several are selected, largest one is used.
+//!
+//!`stream` feature removes per record buffer from C stdio and hook loggers, writing each formatted piece directly instead,
+//!so that only small prefix buffer is placed on stack. Web and Android loggers require complete message and keep buffering.
 //!
 //!#### Compile time macros
 //!
+//!Maximum level can be set for whole build by `ROGU_MAX_LEVEL` environment variable (e.g. `ROGU_MAX_LEVEL=info`),
+//!with `ROGU_RELEASE_MAX_LEVEL` taking priority in release mode. It is available as [STATIC_MAX_LEVEL](constant.STATIC_MAX_LEVEL.html) and, unlike features, it is not affected by feature unification.
+//!
+//!Alternatively, macros can be disabled by features:
+//!
+//!- `level_critical_off` - Disables `critical!` in debug mode;
 //!- `level_error_off` - Disables `error!` in debug mode;
 //!- `level_warn_off` - Disables `warn!` in debug mode;
+//!- `level_notice_off` - Disables `notice!` in debug mode;
 //!- `level_info_off` - Disables `info!` in debug mode;
 //!- `level_debug_off` - Disables `debug!` in debug mode;
 //!- `level_trace_off` - Disables `trace!` in debug mode;
 //!- `level_all_off`- Disables all macros in debug mode;
 //!
+//!- `release_level_critical_off` - Disables `critical!` in release mode;
 //!- `release_level_error_off` - Disables `error!` in release mode;
 //!- `release_level_warn_off` - Disables `warn!` in release mode;
+//!- `release_level_notice_off` - Disables `notice!` in release mode;
 //!- `release_level_info_off` - Disables `info!` in release mode;
 //!- `release_level_debug_off` - Disables `debug!` in release mode;
 //!- `release_level_trace_off` - Disables `trace!` in release mode;
 //!- `release_level_all_off`- Disables all macros in release mode;
+//!
+//!`fatal!` is never disabled: it writes record regardless of level, flushes sinks and terminates program (see [set_fatal_handler](fn.set_fatal_handler.html)).
 
 #![warn(missing_docs)]
 #![no_std]
 
+#[cfg(any(feature = "std", feature = "tracing"))]
+extern crate std;
+
+#[cfg(feature = "std")]
+pub mod clock;
 #[cfg(feature = "log")]
 mod rust_log;
-#[cfg(all(feature = "std", not(any(all(target_arch = "wasm32", target_os = "unknown"), target_os = "android"))))]
+#[cfg(feature = "log_facade")]
+#[doc(hidden)]
+pub use rust_log::{enabled as __log_enabled, forward as __log_forward};
+#[cfg(feature = "tracing")]
+pub mod tracing;
+#[cfg(all(feature = "std", not(any(target_os = "unknown", target_os = "android", target_os = "none", feature = "hook"))))]
 mod time;
 mod data;
+mod dynamic;
+mod filter;
+mod fatal;
+mod level;
 mod out;
 mod rt;
+pub use filter::{set_target_level, is_target_enabled, MAX_TARGETS};
+pub use fatal::set_fatal_handler;
+#[doc(hidden)]
+pub use dynamic::enabled as __enabled;
+pub use level::{LevelFilter, ParseLevelError};
+#[doc(hidden)]
+pub use fatal::terminate as __fatal_terminate;
 #[doc(hidden)]
 pub use out::Out;
+#[cfg(not(all(not(target_arch = "wasm32"), target_os = "unknown", not(feature = "hook"))))]
+#[doc(hidden)]
+pub use out::{Buffer as __Buffer, CONTINUATION as __CONTINUATION};
+pub use out::set_deterministic;
+#[doc(hidden)]
+pub use out::is_direct as __is_platform_direct;
+#[cfg(any(target_os = "none", feature = "hook"))]
+pub use out::set_write_hook;
+#[cfg(all(feature = "std", not(any(target_os = "unknown", target_os = "android", target_os = "none", feature = "hook"))))]
+pub use out::set_print;
+#[cfg(not(feature = "ufmt"))]
+pub mod sink;
+#[cfg(not(feature = "ufmt"))]
+pub mod context;
+#[cfg(any(feature = "binary", feature = "decoder"))]
+pub mod binary;
+#[cfg(all(feature = "ring", not(feature = "ufmt")))]
+pub mod ring;
+#[cfg(all(feature = "ring", feature = "ufmt"))]
+compile_error!("ring feature requires sinks, which are not available with ufmt");
+#[cfg(all(feature = "deferred", not(feature = "ufmt")))]
+pub mod deferred;
+#[cfg(all(feature = "deferred", feature = "ufmt"))]
+compile_error!("deferred feature is not available with ufmt");
+#[cfg(all(feature = "testing", not(feature = "ufmt")))]
+pub mod testing;
+#[cfg(all(feature = "testing", any(feature = "ufmt", feature = "binary")))]
+compile_error!("testing feature requires sinks, which are not available with ufmt or binary");
 #[cfg(feature = "ufmt")]
-mod ufmt;
+#[doc(hidden)]
+pub mod ufmt;
 #[cfg(not(feature = "ufmt"))]
 mod cor;
+#[doc(hidden)]
+pub mod rate;
 
 #[cfg(feature = "ufmt")]
 use crate::ufmt::derive::uDebug as Debug;
@@ -58,22 +190,48 @@ use core::sync::atomic::{AtomicU8, Ordering};
 
 static LEVEL: AtomicU8 = AtomicU8::new(0);
 
+include!(concat!(env!("OUT_DIR"), "/static_max_level.rs"));
+
+///Maximum level, that can be enabled, set at compile time.
+///
+///It is specified by `ROGU_MAX_LEVEL` environment variable (`ROGU_RELEASE_MAX_LEVEL` takes priority without `debug_assertions`), defaulting to `TRACE`.
+///Records above it are never enabled, so that their macros are eliminated by optimizer.
+pub const STATIC_MAX_LEVEL: LevelFilter = LevelFilter::from_raw(STATIC_MAX_LEVEL_RAW);
+
+#[inline(always)]
+const fn is_static_enabled(level: Level) -> bool {
+    level as u8 == Level::FATAL as u8 || level as u8 <= STATIC_MAX_LEVEL as u8
+}
+
 ///Logging levels
+///
+///Levels are ordered by verbosity, i.e. `FATAL` is the least and `TRACE` is the greatest.
+///
+///Level is parsed from case-insensitive name or its number, which is syslog severity (`TRACE` is `8`).
 #[repr(u8)]
+#[non_exhaustive]
 #[derive(Copy, Eq, Debug)]
 pub enum Level {
     #[doc(hidden)]
     NONE = 0,
+    /// Designates unrecoverable errors, after which program is terminated.
+    FATAL = 1,
+    #[cfg(feature = "extended_levels")]
+    /// Designates critical conditions, that require immediate attention.
+    CRITICAL = 2,
     /// Designates very serious errors.
-    ERROR = 1,
+    ERROR = 3,
     /// Designates hazardous situations.
-    WARN,
+    WARN = 4,
+    #[cfg(feature = "extended_levels")]
+    /// Designates normal, but significant conditions.
+    NOTICE = 5,
     /// Designates useful information.
-    INFO,
+    INFO = 6,
     /// Designates lower priority information.
-    DEBUG,
+    DEBUG = 7,
     /// Designates very low priority, often extremely verbose, information.
-    TRACE,
+    TRACE = 8,
 }
 
 impl Clone for Level {
@@ -90,19 +248,51 @@ impl PartialEq for Level {
     }
 }
 
+impl Level {
+    #[allow(unused)]
+    const fn from_raw(level: u8) -> Self {
+        match level {
+            0 => Level::NONE,
+            1 => Level::FATAL,
+            #[cfg(feature = "extended_levels")]
+            2 => Level::CRITICAL,
+            3 => Level::ERROR,
+            4 => Level::WARN,
+            #[cfg(feature = "extended_levels")]
+            5 => Level::NOTICE,
+            6 => Level::INFO,
+            7 => Level::DEBUG,
+            _ => Level::TRACE,
+        }
+    }
+}
+
 ///Sets logging level
-pub fn set_level(level: Level) {
+pub fn set_level<L: Into<LevelFilter>>(level: L) {
     rt::init();
-    LEVEL.store(level as u8, Ordering::Relaxed);
+    LEVEL.store(level.into() as u8, Ordering::Relaxed);
+    on_level_change();
+}
 
+#[inline]
+///Returns current logging level
+pub fn level() -> LevelFilter {
+    LevelFilter::from_raw(LEVEL.load(Ordering::Relaxed))
+}
+
+#[inline]
+fn on_level_change() {
     #[cfg(feature = "log")]
     {
-        rust_log::init(level.into());
+        let level = core::cmp::max(LEVEL.load(Ordering::Relaxed), filter::max_level());
+        rust_log::init(Level::from_raw(level).into());
     }
 }
 
 #[inline]
 ///Checks if logging is enabled
+///
+///`FATAL` is always enabled, while levels above [STATIC_MAX_LEVEL](constant.STATIC_MAX_LEVEL.html) are never enabled.
 pub fn is_enabled(level: Level) -> bool {
-    LEVEL.load(Ordering::Relaxed) >= level as u8
+    level as u8 == Level::FATAL as u8 || (is_static_enabled(level) && LEVEL.load(Ordering::Relaxed) >= level as u8)
 }
@@ -1,5 +1,5 @@
-use core::{cmp, mem, ptr};
 use crate::data;
+use super::buffer::{self, Buffer};
 
 #[allow(unused)]
 #[derive(Clone, Copy)]
@@ -16,7 +16,11 @@ enum LogPriority {
     SILENT = 8,
 }
 
-const MSG_MAX_LEN: usize = 4000;
+//Limit of logcat message length
+const MSG_MAX_LEN: usize = match buffer::capacity(4000) {
+    capacity if capacity > 4000 => 4000,
+    capacity => capacity,
+};
 const TAG: &[u8; 5] = b"Rust\0";
 
 #[link(name = "log")]
@@ -26,39 +30,31 @@ extern "C" {
 
 pub struct Log {
     prio: LogPriority,
-    buffer: mem::MaybeUninit<[u8; MSG_MAX_LEN + 1]>,
-    len: usize,
+    buffer: Buffer<MSG_MAX_LEN>,
 }
 
 impl Log {
     fn new(prio: LogPriority, level: &'static str, location: &'static str) -> Self {
         let mut res = Self {
             prio,
-            buffer: mem::MaybeUninit::uninit(),
-            len: 0,
+            buffer: Buffer::new(),
         };
 
-        unsafe {
-            ptr::copy_nonoverlapping(level.as_ptr(), res.as_mut_ptr(), level.len())
-        }
-        res.len += level.len();
-
-        unsafe {
-            ptr::copy_nonoverlapping(location.as_ptr(), res.as_mut_ptr().add(res.len), location.len())
-        }
-        res.len += location.len();
+        res.write_text(level);
+        super::write_location(location, |text| res.write_text(text));
 
         res
     }
 
     #[inline(always)]
-    fn as_ptr(&self) -> *const u8 {
-        self.buffer.as_ptr() as *const u8
+    pub fn fatal(location: &'static str) -> Self {
+        Self::new(LogPriority::FATAL, data::level::FATAL, location)
     }
 
+    #[cfg(feature = "extended_levels")]
     #[inline(always)]
-    fn as_mut_ptr(&mut self) -> *mut u8 {
-        self.buffer.as_mut_ptr() as *mut u8
+    pub fn critical(location: &'static str) -> Self {
+        Self::new(LogPriority::ERROR, data::level::CRITICAL, location)
     }
 
     #[inline(always)]
@@ -71,6 +67,12 @@ impl Log {
         Self::new(LogPriority::WARN, data::level::WARN, location)
     }
 
+    #[cfg(feature = "extended_levels")]
+    #[inline(always)]
+    pub fn notice(location: &'static str) -> Self {
+        Self::new(LogPriority::INFO, data::level::NOTICE, location)
+    }
+
     #[inline(always)]
     pub fn info(location: &'static str) -> Self {
         Self::new(LogPriority::INFO, data::level::INFO, location)
@@ -86,39 +88,13 @@ impl Log {
         Self::new(LogPriority::VERBOSE, data::level::TRACE, location)
     }
 
-    pub fn flush(&mut self) {
-        unsafe {
-            self.as_mut_ptr().add(self.len - 1).write(0);
-            __android_log_write(self.prio as i32, TAG.as_ptr() as *const _, self.as_ptr() as *const _);
-        }
-
-        self.len = 0;
-    }
-
-    #[inline]
-    fn copy_text<'a>(&mut self, text: &'a str) -> &'a str {
-        let write_len = cmp::min(MSG_MAX_LEN.saturating_sub(self.len), text.len());
-        unsafe {
-            ptr::copy_nonoverlapping(text.as_ptr(), self.as_mut_ptr().add(self.len), write_len);
-        }
-        self.len += write_len;
-        &text[write_len..]
-    }
-
-    pub fn write_text(&mut self, mut text: &str) {
-        loop {
-            text = self.copy_text(text);
-
-            if text.len() == 0 {
-                break;
-            } else {
-                self.flush();
-            }
-        }
-
-        if unsafe { *self.as_ptr().add(self.len - 1) } == b'\n' {
-            self.flush();
-        }
+    pub fn write_text(&mut self, text: &str) {
+        let prio = self.prio;
+        self.buffer.write_text(text, |text| unsafe {
+            //Record ends with newline, which is replaced with terminating NUL.
+            text[text.len() - 1] = 0;
+            __android_log_write(prio as i32, TAG.as_ptr() as *const _, text.as_ptr() as *const _);
+        });
     }
 }
 
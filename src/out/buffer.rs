@@ -0,0 +1,183 @@
+//!Record buffer shared by writers
+//In streaming mode, only Web and Android use record buffer.
+#![cfg_attr(feature = "stream", allow(dead_code))]
+
+use core::{mem, ptr, slice};
+
+///Prefix of chunks, continuing record that did not fit into buffer.
+pub const CONTINUATION: &str = "... ";
+
+///Returns buffer capacity selected by `buffer_*` feature or `default`, if none is selected.
+pub const fn capacity(default: usize) -> usize {
+    if cfg!(feature = "buffer_16384") {
+        16384
+    } else if cfg!(feature = "buffer_1024") {
+        1024
+    } else if cfg!(feature = "buffer_256") {
+        256
+    } else {
+        default
+    }
+}
+
+#[inline]
+fn floor_char_boundary(text: &str, mut idx: usize) -> usize {
+    while !text.is_char_boundary(idx) {
+        idx -= 1;
+    }
+    idx
+}
+
+///Returns length of `text` prefix to be written into `available` bytes.
+///
+///Prefers to split after newline, then after whitespace, then at char boundary.
+fn split_point(text: &str, available: usize) -> usize {
+    let text = &text[..floor_char_boundary(text, available)];
+
+    if let Some(idx) = text.rfind('\n') {
+        idx + 1
+    } else if let Some((idx, ch)) = text.char_indices().rev().find(|(_, ch)| ch.is_whitespace()) {
+        idx + ch.len_utf8()
+    } else {
+        text.len()
+    }
+}
+
+///Fixed size buffer, accumulating record text before passing it to the writer.
+///
+///Every flushed chunk is valid UTF-8 and ends with newline.
+///If record does not fit, it is split into several chunks, each continuation chunk starting with [CONTINUATION](constant.CONTINUATION.html).
+pub struct Buffer<const N: usize> {
+    buffer: mem::MaybeUninit<[u8; N]>,
+    len: usize,
+}
+
+impl<const N: usize> Buffer<N> {
+    //Newline and continuation marker must leave space for at least one character.
+    const _CAPACITY_CHECK: () = assert!(N >= 1 + CONTINUATION.len() + 4);
+
+    #[inline(always)]
+    ///Creates empty buffer.
+    pub const fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::_CAPACITY_CHECK;
+
+        Self {
+            buffer: mem::MaybeUninit::uninit(),
+            len: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn as_mut_ptr(&mut self) -> *mut u8 {
+        self.buffer.as_mut_ptr() as *mut u8
+    }
+
+    #[inline(always)]
+    ///Returns written bytes.
+    pub fn as_mut_bytes(&mut self) -> &mut [u8] {
+        unsafe {
+            slice::from_raw_parts_mut(self.as_mut_ptr(), self.len)
+        }
+    }
+
+    #[inline]
+    fn push(&mut self, text: &str) {
+        unsafe {
+            ptr::copy_nonoverlapping(text.as_ptr(), self.as_mut_ptr().add(self.len), text.len());
+        }
+        self.len += text.len();
+    }
+
+    #[inline]
+    ///Passes written bytes to `flush` and clears buffer.
+    pub fn flush<F: FnMut(&mut [u8])>(&mut self, mut flush: F) {
+        flush(self.as_mut_bytes());
+        self.len = 0;
+    }
+
+    ///Writes `text`, flushing whenever buffer is full or record is complete.
+    pub fn write_text<F: FnMut(&mut [u8])>(&mut self, mut text: &str, mut flush: F) {
+        loop {
+            let available = N - 1 - self.len;
+            if text.len() <= available {
+                self.push(text);
+                break;
+            }
+
+            let split = split_point(text, available);
+            self.push(&text[..split]);
+            text = &text[split..];
+
+            if self.as_mut_bytes().last() != Some(&b'\n') {
+                self.push("\n");
+            }
+            self.flush(&mut flush);
+            self.push(CONTINUATION);
+        }
+
+        if self.as_mut_bytes().last() == Some(&b'\n') {
+            self.flush(flush);
+        }
+    }
+}
+
+impl<const N: usize> Default for Buffer<N> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "stream")]
+///Streaming writer, passing text as it is written.
+///
+///Small pieces (e.g. level and location) are accumulated in buffer of `N` bytes, to reduce number of writes,
+///while pieces, that do not fit, are passed directly.
+pub struct Stream<const N: usize> {
+    buffer: mem::MaybeUninit<[u8; N]>,
+    len: usize,
+}
+
+#[cfg(feature = "stream")]
+impl<const N: usize> Stream<N> {
+    #[inline(always)]
+    pub const fn new() -> Self {
+        Self {
+            buffer: mem::MaybeUninit::uninit(),
+            len: 0,
+        }
+    }
+
+    #[inline(always)]
+    fn as_bytes(&self) -> &[u8] {
+        unsafe {
+            slice::from_raw_parts(self.buffer.as_ptr() as *const u8, self.len)
+        }
+    }
+
+    #[inline]
+    fn flush<F: FnMut(&[u8])>(&mut self, mut flush: F) {
+        if self.len > 0 {
+            flush(self.as_bytes());
+            self.len = 0;
+        }
+    }
+
+    ///Writes `text`, flushing when record is complete.
+    pub fn write_text<F: FnMut(&[u8])>(&mut self, text: &str, mut flush: F) {
+        if text.len() <= N - self.len {
+            unsafe {
+                ptr::copy_nonoverlapping(text.as_ptr(), (self.buffer.as_mut_ptr() as *mut u8).add(self.len), text.len());
+            }
+            self.len += text.len();
+        } else {
+            self.flush(&mut flush);
+            flush(text.as_bytes());
+        }
+
+        if text.ends_with('\n') {
+            self.flush(flush);
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//!User hook based writer for bare-metal targets
+
+use crate::data;
+use core::{mem, ptr};
+use core::sync::atomic::{AtomicPtr, Ordering};
+
+#[cfg(not(feature = "stream"))]
+type RecordBuffer = super::buffer::Buffer<{ super::buffer::capacity(512) }>;
+#[cfg(feature = "stream")]
+type RecordBuffer = super::buffer::Stream<64>;
+
+static HOOK: AtomicPtr<()> = AtomicPtr::new(ptr::null_mut());
+
+///Sets function to write formatted records into (e.g. UART, semihosting or RTT).
+///
+///Until hook is set, records are discarded.
+pub fn set_write_hook(hook: fn(&[u8])) {
+    HOOK.store(hook as *mut (), Ordering::Release);
+}
+
+#[cfg(feature = "binary")]
+#[inline]
+///Writes raw bytes into hook, if any.
+pub fn write(bytes: &[u8]) {
+    if let Some(hook) = get_hook() {
+        hook(bytes);
+    }
+}
+
+#[inline(always)]
+fn get_hook() -> Option<fn(&[u8])> {
+    let hook = HOOK.load(Ordering::Acquire);
+    match hook.is_null() {
+        true => None,
+        false => Some(unsafe { mem::transmute::<*mut (), fn(&[u8])>(hook) }),
+    }
+}
+
+pub struct Hook {
+    buffer: RecordBuffer,
+}
+
+impl Hook {
+    fn new(level: &'static str, location: &'static str) -> Self {
+        let mut res = Self {
+            buffer: RecordBuffer::new(),
+        };
+
+        res.write_text(level);
+        super::write_location(location, |text| res.write_text(text));
+
+        res
+    }
+
+    #[inline(always)]
+    pub fn fatal(location: &'static str) -> Self {
+        Self::new(data::level::FATAL, location)
+    }
+
+    #[cfg(feature = "extended_levels")]
+    #[inline(always)]
+    pub fn critical(location: &'static str) -> Self {
+        Self::new(data::level::CRITICAL, location)
+    }
+
+    #[inline(always)]
+    pub fn error(location: &'static str) -> Self {
+        Self::new(data::level::ERROR, location)
+    }
+
+    #[inline(always)]
+    pub fn warn(location: &'static str) -> Self {
+        Self::new(data::level::WARN, location)
+    }
+
+    #[cfg(feature = "extended_levels")]
+    #[inline(always)]
+    pub fn notice(location: &'static str) -> Self {
+        Self::new(data::level::NOTICE, location)
+    }
+
+    #[inline(always)]
+    pub fn info(location: &'static str) -> Self {
+        Self::new(data::level::INFO, location)
+    }
+
+    #[inline(always)]
+    pub fn debug(location: &'static str) -> Self {
+        Self::new(data::level::DEBUG, location)
+    }
+
+    #[inline(always)]
+    pub fn trace(location: &'static str) -> Self {
+        Self::new(data::level::TRACE, location)
+    }
+
+    #[inline]
+    fn write_text(&mut self, text: &str) {
+        let hook = get_hook();
+        self.buffer.write_text(text, |text| if let Some(hook) = hook {
+            hook(text);
+        });
+    }
+}
+
+#[cfg(feature = "ufmt")]
+impl ufmt::uWrite for Hook {
+    type Error = core::convert::Infallible;
+
+    #[inline]
+    fn write_str(&mut self, text: &str) -> Result<(), Self::Error> {
+        self.write_text(text);
+
+        Ok(())
+    }
+}
+
+#[cfg(not(feature = "ufmt"))]
+impl core::fmt::Write for Hook {
+    #[inline]
+    fn write_str(&mut self, text: &str) -> core::fmt::Result {
+        self.write_text(text);
+
+        Ok(())
+    }
+}
@@ -1,23 +1,109 @@
-#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+#[cfg(not(all(not(target_arch = "wasm32"), target_os = "unknown", not(feature = "hook"))))]
+mod buffer;
+#[cfg(not(all(not(target_arch = "wasm32"), target_os = "unknown", not(feature = "hook"))))]
+pub use buffer::{Buffer, CONTINUATION};
+
+#[cfg(all(target_arch = "wasm32", target_os = "unknown", not(feature = "hook")))]
 mod wasm;
-#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+#[cfg(all(target_arch = "wasm32", target_os = "unknown", not(feature = "hook")))]
 ///Alias to platform logger
 pub type Out = wasm::Console;
 
-#[cfg(target_os = "android")]
+#[cfg(all(target_os = "android", not(feature = "hook")))]
 mod android;
-#[cfg(target_os = "android")]
+#[cfg(all(target_os = "android", not(feature = "hook")))]
 ///Alias to platform logger
 pub type Out = android::Log;
 
-#[cfg(not(any(all(target_arch = "wasm32", target_os = "unknown"), target_os = "android")))]
+#[cfg(any(target_os = "none", feature = "hook"))]
+pub(crate) mod hook;
+#[cfg(any(target_os = "none", feature = "hook"))]
+pub use hook::set_write_hook;
+#[cfg(any(target_os = "none", feature = "hook"))]
+///Alias to user hook logger
+pub type Out = hook::Hook;
+
+#[cfg(not(any(target_os = "unknown", target_os = "android", target_os = "none", feature = "hook")))]
 mod std_c;
-#[cfg(not(any(all(target_arch = "wasm32", target_os = "unknown"), target_os = "android")))]
+#[cfg(all(feature = "std", not(any(target_os = "unknown", target_os = "android", target_os = "none", feature = "hook"))))]
+pub use std_c::set_print;
+#[cfg(not(any(target_os = "unknown", target_os = "android", target_os = "none", feature = "hook")))]
 ///Alias to platform logger
 pub type Out = std_c::FdWriter;
 
-#[cfg(all(not(target_arch = "wasm32"), target_os = "unknown"))]
+#[cfg(all(not(target_arch = "wasm32"), target_os = "unknown", not(feature = "hook")))]
 mod noop;
-#[cfg(all(not(target_arch = "wasm32"), target_os = "unknown"))]
+#[cfg(all(not(target_arch = "wasm32"), target_os = "unknown", not(feature = "hook")))]
 ///Alias to noop logger
 pub type Out = noop::Noop;
+
+static IS_DIRECT: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(true);
+
+#[doc(hidden)]
+#[inline]
+///Returns whether records are written to platform logger directly, rather than through sink.
+pub fn is_direct() -> bool {
+    IS_DIRECT.load(core::sync::atomic::Ordering::Acquire)
+}
+
+#[allow(unused)]
+#[inline]
+pub(crate) fn set_direct(is_direct: bool) {
+    IS_DIRECT.store(is_direct, core::sync::atomic::Ordering::Release);
+}
+
+#[allow(unused)]
+///Creates platform logger of `level`.
+pub(crate) fn by_level(level: crate::Level, location: &'static str) -> Out {
+    match level {
+        crate::Level::FATAL => Out::fatal(location),
+        #[cfg(feature = "extended_levels")]
+        crate::Level::CRITICAL => Out::critical(location),
+        crate::Level::ERROR => Out::error(location),
+        crate::Level::WARN => Out::warn(location),
+        #[cfg(feature = "extended_levels")]
+        crate::Level::NOTICE => Out::notice(location),
+        crate::Level::INFO => Out::info(location),
+        crate::Level::DEBUG => Out::debug(location),
+        crate::Level::TRACE | crate::Level::NONE => Out::trace(location),
+    }
+}
+
+static DETERMINISTIC: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+
+///Sets deterministic output mode, which omits timestamps and strips directories from source file paths.
+///
+///Intended for snapshot tests of output.
+pub fn set_deterministic(enabled: bool) {
+    DETERMINISTIC.store(enabled, core::sync::atomic::Ordering::Relaxed);
+}
+
+#[inline]
+pub(crate) fn is_deterministic() -> bool {
+    DETERMINISTIC.load(core::sync::atomic::Ordering::Relaxed)
+}
+
+#[inline]
+///Returns source file path to output.
+pub(crate) fn file_name(path: &str) -> &str {
+    match is_deterministic() {
+        true => match path.rfind(['/', '\\']) {
+            Some(idx) => &path[idx + 1..],
+            None => path,
+        },
+        false => path,
+    }
+}
+
+#[allow(unused)]
+#[inline]
+///Writes location prefix `- [file:line] - `, normalizing its path in deterministic mode.
+fn write_location<F: FnMut(&str)>(location: &str, mut write: F) {
+    match location.strip_prefix("- [") {
+        Some(rest) if is_deterministic() => {
+            write("- [");
+            write(file_name(rest));
+        },
+        _ => write(location),
+    }
+}
@@ -2,6 +2,19 @@ pub struct Noop {
 }
 
 impl Noop {
+    #[inline(always)]
+    pub fn fatal(_: &'static str) -> Self {
+        Self {
+        }
+    }
+
+    #[cfg(feature = "extended_levels")]
+    #[inline(always)]
+    pub fn critical(_: &'static str) -> Self {
+        Self {
+        }
+    }
+
     #[inline(always)]
     pub fn error(_: &'static str) -> Self {
         Self {
@@ -14,6 +27,13 @@ impl Noop {
         }
     }
 
+    #[cfg(feature = "extended_levels")]
+    #[inline(always)]
+    pub fn notice(_: &'static str) -> Self {
+        Self {
+        }
+    }
+
     #[inline(always)]
     pub fn info(_: &'static str) -> Self {
         Self {
@@ -35,19 +55,19 @@ impl Noop {
 }
 
 #[cfg(feature = "ufmt")]
-impl ufmt::uWrite for FdWriter {
+impl ufmt::uWrite for Noop {
     type Error = core::convert::Infallible;
 
     #[inline(always)]
-    fn write_str(&mut self, text: &str) -> Result<(), Self::Error> {
+    fn write_str(&mut self, _: &str) -> Result<(), Self::Error> {
         Ok(())
     }
 }
 
 #[cfg(not(feature = "ufmt"))]
-impl core::fmt::Write for FdWriter {
+impl core::fmt::Write for Noop {
     #[inline(always)]
-    fn write_str(&mut self, text: &str) -> core::fmt::Result {
+    fn write_str(&mut self, _: &str) -> core::fmt::Result {
         Ok(())
     }
 }
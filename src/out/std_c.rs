@@ -1,58 +1,77 @@
-///C stdlib baesd writer into stdout/stderr
+//!C stdlib based writer into stdout/stderr
 
 use crate::data;
-use core::{mem, cmp, ptr};
 
-const BUFFER_CAPACITY: usize = 4096;
+#[cfg(feature = "std")]
+static PRINT: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+
+#[cfg(feature = "std")]
+///Sets whether stdout/stderr are written through `print!`/`eprint!` instead of C stdio.
+///
+///This allows test harness to capture output per test, showing it only for failed tests.
+///
+///Disabled by default, as rogu cannot know whether it is used by tests, so tests must enable it explicitly.
+pub fn set_print(enabled: bool) {
+    PRINT.store(enabled, core::sync::atomic::Ordering::Relaxed);
+}
+
+#[cfg(feature = "std")]
+#[inline]
+fn print(fd: u8, text: &[u8]) -> bool {
+    if !PRINT.load(core::sync::atomic::Ordering::Relaxed) {
+        return false;
+    }
+
+    //Text is always split on char boundaries
+    let text = unsafe {
+        core::str::from_utf8_unchecked(text)
+    };
+    match fd {
+        2 => std::eprint!("{}", text),
+        _ => std::print!("{}", text),
+    }
+    true
+}
+
+#[cfg(not(feature = "stream"))]
+type RecordBuffer = super::buffer::Buffer<{ super::buffer::capacity(4096) }>;
+#[cfg(feature = "stream")]
+type RecordBuffer = super::buffer::Stream<128>;
 
 pub struct FdWriter {
     fd: u8,
-    buffer: mem::MaybeUninit<[u8; BUFFER_CAPACITY]>,
-    len: usize,
+    buffer: RecordBuffer,
 }
 
 impl FdWriter {
     fn new(fd: u8, level: &'static str, location: &'static str) -> Self {
         let mut res = Self {
             fd,
-            buffer: mem::MaybeUninit::uninit(),
-            len: 0,
+            buffer: RecordBuffer::new(),
         };
 
-        unsafe {
-            ptr::copy_nonoverlapping(level.as_ptr(), res.buffer_as_mut_ptr(), level.len())
-        }
-        res.len += level.len();
+        res.write_text(level);
 
         #[cfg(feature = "std")]
-        {
+        if !super::is_deterministic() {
             let time = crate::time::get();
-            let time_str = time.as_str();
-
-            unsafe {
-                ptr::copy_nonoverlapping(time_str.as_ptr(), res.buffer_as_mut_ptr().add(res.len), time_str.len())
-            }
-            res.len += time_str.len();
+            res.write_text(time.as_str());
         }
 
-        unsafe {
-            ptr::copy_nonoverlapping(location.as_ptr(), res.buffer_as_mut_ptr().add(res.len), location.len())
-        }
-        res.len += location.len();
+        super::write_location(location, |text| res.write_text(text));
 
         res
     }
 
-    fn buffer(&self) -> &[u8; BUFFER_CAPACITY] {
-        unsafe {
-            &*(self.buffer.as_ptr())
-        }
+    #[inline(always)]
+    pub fn fatal(location: &'static str) -> Self {
+        Self::new(2, data::level::FATAL, location)
     }
 
-    fn buffer_as_mut_ptr(&mut self) -> *mut u8 {
-        unsafe {
-            (*(self.buffer.as_mut_ptr())).as_mut_ptr()
-        }
+    #[cfg(feature = "extended_levels")]
+    #[inline(always)]
+    pub fn critical(location: &'static str) -> Self {
+        Self::new(2, data::level::CRITICAL, location)
     }
 
     #[inline(always)]
@@ -65,6 +84,12 @@ impl FdWriter {
         Self::new(2, data::level::WARN, location)
     }
 
+    #[cfg(feature = "extended_levels")]
+    #[inline(always)]
+    pub fn notice(location: &'static str) -> Self {
+        Self::new(1, data::level::NOTICE, location)
+    }
+
     #[inline(always)]
     pub fn info(location: &'static str) -> Self {
         Self::new(1, data::level::INFO, location)
@@ -80,40 +105,19 @@ impl FdWriter {
         Self::new(1, data::level::TRACE, location)
     }
 
-    fn flush(&mut self) {
-        let text = unsafe {
-            core::str::from_utf8_unchecked(&self.buffer()[..self.len])
-        };
-        unsafe {
-            libc::write(self.fd.into(), text.as_ptr() as *const _, text.len() as _);
-        }
-        self.len = 0;
-    }
-
     #[inline]
-    fn copy_text<'a>(&mut self, text: &'a str) -> &'a str {
-        let write_len = cmp::min(BUFFER_CAPACITY.saturating_sub(self.len), text.len());
-        unsafe {
-            ptr::copy_nonoverlapping(text.as_ptr(), self.buffer_as_mut_ptr().add(self.len), write_len);
-        }
-        self.len += write_len;
-        &text[write_len..]
-    }
-
-    fn write_text(&mut self, mut text: &str) {
-        loop {
-            text = self.copy_text(text);
-
-            if text.len() == 0 {
-                break;
-            } else {
-                self.flush();
+    fn write_text(&mut self, text: &str) {
+        let fd = self.fd;
+        self.buffer.write_text(text, |text| {
+            #[cfg(feature = "std")]
+            if print(fd, text) {
+                return;
             }
-        }
 
-        if self.buffer()[self.len - 1] == b'\n' {
-            self.flush();
-        }
+            unsafe {
+                libc::write(fd.into(), text.as_ptr() as *const _, text.len() as _);
+            }
+        });
     }
 }
 
@@ -1,7 +1,5 @@
 use wasm_bindgen::prelude::wasm_bindgen;
 
-use core::{mem, ptr, cmp};
-
 #[wasm_bindgen]
 extern "C" {
     #[wasm_bindgen(js_namespace = console)]
@@ -17,44 +15,37 @@ extern "C" {
 }
 
 use crate::data;
+use super::buffer::{self, Buffer};
 
-const BUFFER_SIZE: usize = 4096;
+const BUFFER_SIZE: usize = buffer::capacity(4096);
 
 pub struct Console {
     fun: fn(&str),
-    buffer: mem::MaybeUninit<[u8; BUFFER_SIZE]>,
-    len: usize,
+    buffer: Buffer<BUFFER_SIZE>,
 }
 
 impl Console {
     fn new(fun: fn(&str), level: &'static str, location: &'static str) -> Self {
         let mut res = Self {
             fun,
-            buffer: mem::MaybeUninit::uninit(),
-            len: 0,
+            buffer: Buffer::new(),
         };
 
-        unsafe {
-            ptr::copy_nonoverlapping(level.as_ptr(), res.as_mut_ptr(), level.len())
-        }
-        res.len += level.len();
-
-        unsafe {
-            ptr::copy_nonoverlapping(location.as_ptr(), res.as_mut_ptr().add(res.len), location.len())
-        }
-        res.len += location.len();
+        res.write_text(level);
+        super::write_location(location, |text| res.write_text(text));
 
         res
     }
 
     #[inline(always)]
-    fn as_ptr(&self) -> *const u8 {
-        self.buffer.as_ptr() as *const u8
+    pub fn fatal(location: &'static str) -> Self {
+        Self::new(error, data::level::FATAL, location)
     }
 
+    #[cfg(feature = "extended_levels")]
     #[inline(always)]
-    fn as_mut_ptr(&mut self) -> *mut u8 {
-        self.buffer.as_mut_ptr() as *mut u8
+    pub fn critical(location: &'static str) -> Self {
+        Self::new(error, data::level::CRITICAL, location)
     }
 
     #[inline(always)]
@@ -67,6 +58,12 @@ impl Console {
         Self::new(warn, data::level::WARN, location)
     }
 
+    #[cfg(feature = "extended_levels")]
+    #[inline(always)]
+    pub fn notice(location: &'static str) -> Self {
+        Self::new(info, data::level::NOTICE, location)
+    }
+
     #[inline(always)]
     pub fn info(location: &'static str) -> Self {
         Self::new(info, data::level::INFO, location)
@@ -82,40 +79,17 @@ impl Console {
         Self::new(trace, data::level::TRACE, location)
     }
 
-    pub fn flush(&mut self) {
-        let text = unsafe {
-            let buffer = core::slice::from_raw_parts(self.as_ptr(), self.len);
-            core::str::from_utf8_unchecked(buffer)
-        };
-        (self.fun)(text);
-        self.len = 0;
-    }
-
-    #[inline]
-    fn copy_text<'a>(&mut self, text: &'a str) -> &'a str {
-        let write_len = cmp::min(BUFFER_SIZE.saturating_sub(self.len), text.len());
-        unsafe {
-            ptr::copy_nonoverlapping(text.as_ptr(), self.as_mut_ptr().add(self.len), write_len);
-        }
-        self.len += write_len;
-        &text[write_len..]
-    }
-
-    pub fn write_text(&mut self, mut text: &str) {
-        loop {
-            text = self.copy_text(text);
-
-            if text.len() == 0 {
-                break;
-            } else {
-                self.flush();
-            }
-        }
-
-        if unsafe { *self.as_ptr().add(self.len - 1) } == b'\n' {
-            self.len -= 1;
-            self.flush();
-        }
+    pub fn write_text(&mut self, text: &str) {
+        let fun = self.fun;
+        self.buffer.write_text(text, |text| {
+            let text = match text.last() {
+                Some(b'\n') => &text[..text.len() - 1],
+                _ => text,
+            };
+            fun(unsafe {
+                core::str::from_utf8_unchecked(text)
+            });
+        });
     }
 }
 
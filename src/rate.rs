@@ -0,0 +1,416 @@
+//!Per callsite state of rate limited macros.
+
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+///State of `*_once!` callsite.
+pub struct Once(AtomicBool);
+
+impl Once {
+    #[inline(always)]
+    ///Creates new state.
+    pub const fn new() -> Self {
+        Self(AtomicBool::new(false))
+    }
+
+    #[inline]
+    ///Returns `true` on first call only.
+    pub fn check(&self) -> bool {
+        !self.0.load(Ordering::Relaxed) && !self.0.swap(true, Ordering::Relaxed)
+    }
+}
+
+impl Default for Once {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///State of `*_every_n!` callsite.
+pub struct EveryN(AtomicUsize);
+
+impl EveryN {
+    #[inline(always)]
+    ///Creates new state.
+    pub const fn new() -> Self {
+        Self(AtomicUsize::new(0))
+    }
+
+    #[inline]
+    ///Returns number of suppressed occurrences on every `n`-th call, starting with the first one.
+    pub fn check(&self, n: usize) -> Option<usize> {
+        let count = self.0.fetch_add(1, Ordering::Relaxed);
+        if n <= 1 {
+            return Some(0);
+        }
+
+        match count % n {
+            0 if count == 0 => Some(0),
+            0 => Some(n - 1),
+            _ => None,
+        }
+    }
+}
+
+impl Default for EveryN {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+///State of `*_every!` callsite.
+pub struct Every {
+    //Monotonic time in nanoseconds of last occurrence, that was written.
+    last: core::sync::atomic::AtomicU64,
+    suppressed: AtomicUsize,
+}
+
+#[cfg(feature = "std")]
+impl Every {
+    const NEVER: u64 = u64::MAX;
+
+    #[inline(always)]
+    ///Creates new state.
+    pub const fn new() -> Self {
+        Self {
+            last: core::sync::atomic::AtomicU64::new(Self::NEVER),
+            suppressed: AtomicUsize::new(0),
+        }
+    }
+
+    ///Returns number of suppressed occurrences, if at least `interval` passed since last written occurrence.
+    ///
+    ///Time is taken from [clock::monotonic](../clock/fn.monotonic.html), so that it is not affected by system clock changes.
+    pub fn check(&self, interval: core::time::Duration) -> Option<usize> {
+        let now = crate::clock::monotonic().as_nanos() as u64;
+        let last = self.last.load(Ordering::Relaxed);
+
+        if (last == Self::NEVER || now.saturating_sub(last) >= interval.as_nanos() as u64) && self.last.compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed).is_ok() {
+            Some(self.suppressed.swap(0, Ordering::Relaxed))
+        } else {
+            self.suppressed.fetch_add(1, Ordering::Relaxed);
+            None
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Default for Every {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[doc(hidden)]
+#[inline]
+///Checks if record would be written or deferred by macro of `level`, so that rate state is consumed only by such records.
+pub fn is_active(level: crate::Level, target: Option<&str>) -> bool {
+    let is_enabled = match target {
+        Some(target) => crate::dynamic::target_enabled(target, level),
+        None => crate::dynamic::enabled(level),
+    };
+
+    #[cfg(all(feature = "deferred", not(any(feature = "log_facade", feature = "binary", feature = "ufmt"))))]
+    {
+        is_enabled || crate::deferred::is_deferred(level)
+    }
+    #[cfg(not(all(feature = "deferred", not(any(feature = "log_facade", feature = "binary", feature = "ufmt")))))]
+    {
+        is_enabled
+    }
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_once {
+    ($level:ident, $mac:ident, target: $target:literal, $($arg:tt)*) => {{
+        static STATE: $crate::rate::Once = $crate::rate::Once::new();
+        if $crate::rate::is_active($crate::Level::$level, Some($target)) && STATE.check() {
+            $crate::$mac!(target: $target, $($arg)*);
+        }
+    }};
+    ($level:ident, $mac:ident, target: $target:expr, $($arg:tt)*) => {{
+        static STATE: $crate::rate::Once = $crate::rate::Once::new();
+        match $target {
+            target => if $crate::rate::is_active($crate::Level::$level, Some(target)) && STATE.check() {
+                $crate::$mac!(target: target, $($arg)*);
+            }
+        }
+    }};
+    ($level:ident, $mac:ident, $($arg:tt)*) => {{
+        static STATE: $crate::rate::Once = $crate::rate::Once::new();
+        if $crate::rate::is_active($crate::Level::$level, None) && STATE.check() {
+            $crate::$mac!($($arg)*);
+        }
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_suppressed {
+    ($mac:ident, $suppressed:expr, target: $target:tt, $($arg:tt)*) => {
+        if let Some(suppressed) = $suppressed {
+            let _ = &suppressed;
+            $crate::$mac!(target: $target, $($arg)*);
+            if suppressed > 0 {
+                $crate::$mac!(target: $target, "Suppressed {} similar records", suppressed);
+            }
+        }
+    };
+    ($mac:ident, $suppressed:expr, $($arg:tt)*) => {
+        if let Some(suppressed) = $suppressed {
+            let _ = &suppressed;
+            $crate::$mac!($($arg)*);
+            if suppressed > 0 {
+                $crate::$mac!("Suppressed {} similar records", suppressed);
+            }
+        }
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_every_n {
+    ($level:ident, $mac:ident, $n:expr, target: $target:literal, $($arg:tt)*) => {{
+        static STATE: $crate::rate::EveryN = $crate::rate::EveryN::new();
+        if $crate::rate::is_active($crate::Level::$level, Some($target)) {
+            $crate::__log_suppressed!($mac, STATE.check($n), target: $target, $($arg)*)
+        }
+    }};
+    ($level:ident, $mac:ident, $n:expr, target: $target:expr, $($arg:tt)*) => {{
+        static STATE: $crate::rate::EveryN = $crate::rate::EveryN::new();
+        match $target {
+            target => if $crate::rate::is_active($crate::Level::$level, Some(target)) {
+                $crate::__log_suppressed!($mac, STATE.check($n), target: target, $($arg)*)
+            }
+        }
+    }};
+    ($level:ident, $mac:ident, $n:expr, $($arg:tt)*) => {{
+        static STATE: $crate::rate::EveryN = $crate::rate::EveryN::new();
+        if $crate::rate::is_active($crate::Level::$level, None) {
+            $crate::__log_suppressed!($mac, STATE.check($n), $($arg)*)
+        }
+    }};
+}
+
+#[cfg(feature = "std")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_every {
+    ($level:ident, $mac:ident, $interval:expr, target: $target:literal, $($arg:tt)*) => {{
+        static STATE: $crate::rate::Every = $crate::rate::Every::new();
+        if $crate::rate::is_active($crate::Level::$level, Some($target)) {
+            $crate::__log_suppressed!($mac, STATE.check($interval), target: $target, $($arg)*)
+        }
+    }};
+    ($level:ident, $mac:ident, $interval:expr, target: $target:expr, $($arg:tt)*) => {{
+        static STATE: $crate::rate::Every = $crate::rate::Every::new();
+        match $target {
+            target => if $crate::rate::is_active($crate::Level::$level, Some(target)) {
+                $crate::__log_suppressed!($mac, STATE.check($interval), target: target, $($arg)*)
+            }
+        }
+    }};
+    ($level:ident, $mac:ident, $interval:expr, $($arg:tt)*) => {{
+        static STATE: $crate::rate::Every = $crate::rate::Every::new();
+        if $crate::rate::is_active($crate::Level::$level, None) {
+            $crate::__log_suppressed!($mac, STATE.check($interval), $($arg)*)
+        }
+    }};
+}
+
+#[cfg(feature = "extended_levels")]
+#[macro_export]
+///Writes critical log only once per callsite.
+macro_rules! critical_once {
+    ($($arg:tt)*) => {
+        $crate::__log_once!(CRITICAL, critical, $($arg)*)
+    }
+}
+
+#[cfg(feature = "extended_levels")]
+#[macro_export]
+///Writes critical log on first and every `n`-th call per callsite, followed by number of suppressed calls.
+macro_rules! critical_every_n {
+    ($n:expr, $($arg:tt)*) => {
+        $crate::__log_every_n!(CRITICAL, critical, $n, $($arg)*)
+    }
+}
+
+#[cfg(all(feature = "std", feature = "extended_levels"))]
+#[macro_export]
+///Writes critical log at most once per `Duration` per callsite, followed by number of suppressed calls.
+///
+///Requires `std`.
+macro_rules! critical_every {
+    ($interval:expr, $($arg:tt)*) => {
+        $crate::__log_every!(CRITICAL, critical, $interval, $($arg)*)
+    }
+}
+
+#[macro_export]
+///Writes error log only once per callsite.
+macro_rules! error_once {
+    ($($arg:tt)*) => {
+        $crate::__log_once!(ERROR, error, $($arg)*)
+    }
+}
+
+#[macro_export]
+///Writes error log on first and every `n`-th call per callsite, followed by number of suppressed calls.
+macro_rules! error_every_n {
+    ($n:expr, $($arg:tt)*) => {
+        $crate::__log_every_n!(ERROR, error, $n, $($arg)*)
+    }
+}
+
+#[cfg(feature = "std")]
+#[macro_export]
+///Writes error log at most once per `Duration` per callsite, followed by number of suppressed calls.
+///
+///Requires `std`.
+macro_rules! error_every {
+    ($interval:expr, $($arg:tt)*) => {
+        $crate::__log_every!(ERROR, error, $interval, $($arg)*)
+    }
+}
+
+#[macro_export]
+///Writes warn log only once per callsite.
+macro_rules! warn_once {
+    ($($arg:tt)*) => {
+        $crate::__log_once!(WARN, warn, $($arg)*)
+    }
+}
+
+#[macro_export]
+///Writes warn log on first and every `n`-th call per callsite, followed by number of suppressed calls.
+macro_rules! warn_every_n {
+    ($n:expr, $($arg:tt)*) => {
+        $crate::__log_every_n!(WARN, warn, $n, $($arg)*)
+    }
+}
+
+#[cfg(feature = "std")]
+#[macro_export]
+///Writes warn log at most once per `Duration` per callsite, followed by number of suppressed calls.
+///
+///Requires `std`.
+macro_rules! warn_every {
+    ($interval:expr, $($arg:tt)*) => {
+        $crate::__log_every!(WARN, warn, $interval, $($arg)*)
+    }
+}
+
+#[cfg(feature = "extended_levels")]
+#[macro_export]
+///Writes notice log only once per callsite.
+macro_rules! notice_once {
+    ($($arg:tt)*) => {
+        $crate::__log_once!(NOTICE, notice, $($arg)*)
+    }
+}
+
+#[cfg(feature = "extended_levels")]
+#[macro_export]
+///Writes notice log on first and every `n`-th call per callsite, followed by number of suppressed calls.
+macro_rules! notice_every_n {
+    ($n:expr, $($arg:tt)*) => {
+        $crate::__log_every_n!(NOTICE, notice, $n, $($arg)*)
+    }
+}
+
+#[cfg(all(feature = "std", feature = "extended_levels"))]
+#[macro_export]
+///Writes notice log at most once per `Duration` per callsite, followed by number of suppressed calls.
+///
+///Requires `std`.
+macro_rules! notice_every {
+    ($interval:expr, $($arg:tt)*) => {
+        $crate::__log_every!(NOTICE, notice, $interval, $($arg)*)
+    }
+}
+
+#[macro_export]
+///Writes info log only once per callsite.
+macro_rules! info_once {
+    ($($arg:tt)*) => {
+        $crate::__log_once!(INFO, info, $($arg)*)
+    }
+}
+
+#[macro_export]
+///Writes info log on first and every `n`-th call per callsite, followed by number of suppressed calls.
+macro_rules! info_every_n {
+    ($n:expr, $($arg:tt)*) => {
+        $crate::__log_every_n!(INFO, info, $n, $($arg)*)
+    }
+}
+
+#[cfg(feature = "std")]
+#[macro_export]
+///Writes info log at most once per `Duration` per callsite, followed by number of suppressed calls.
+///
+///Requires `std`.
+macro_rules! info_every {
+    ($interval:expr, $($arg:tt)*) => {
+        $crate::__log_every!(INFO, info, $interval, $($arg)*)
+    }
+}
+
+#[macro_export]
+///Writes debug log only once per callsite.
+macro_rules! debug_once {
+    ($($arg:tt)*) => {
+        $crate::__log_once!(DEBUG, debug, $($arg)*)
+    }
+}
+
+#[macro_export]
+///Writes debug log on first and every `n`-th call per callsite, followed by number of suppressed calls.
+macro_rules! debug_every_n {
+    ($n:expr, $($arg:tt)*) => {
+        $crate::__log_every_n!(DEBUG, debug, $n, $($arg)*)
+    }
+}
+
+#[cfg(feature = "std")]
+#[macro_export]
+///Writes debug log at most once per `Duration` per callsite, followed by number of suppressed calls.
+///
+///Requires `std`.
+macro_rules! debug_every {
+    ($interval:expr, $($arg:tt)*) => {
+        $crate::__log_every!(DEBUG, debug, $interval, $($arg)*)
+    }
+}
+
+#[macro_export]
+///Writes trace log only once per callsite.
+macro_rules! trace_once {
+    ($($arg:tt)*) => {
+        $crate::__log_once!(TRACE, trace, $($arg)*)
+    }
+}
+
+#[macro_export]
+///Writes trace log on first and every `n`-th call per callsite, followed by number of suppressed calls.
+macro_rules! trace_every_n {
+    ($n:expr, $($arg:tt)*) => {
+        $crate::__log_every_n!(TRACE, trace, $n, $($arg)*)
+    }
+}
+
+#[cfg(feature = "std")]
+#[macro_export]
+///Writes trace log at most once per `Duration` per callsite, followed by number of suppressed calls.
+///
+///Requires `std`.
+macro_rules! trace_every {
+    ($interval:expr, $($arg:tt)*) => {
+        $crate::__log_every!(TRACE, trace, $interval, $($arg)*)
+    }
+}
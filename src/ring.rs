@@ -0,0 +1,248 @@
+//!In-memory ring buffer of recent records.
+//!
+//!Ring keeps last records, formatted as `LEVEL - [file:line] - message`, in fixed number of fixed size slots.
+//!Records, that do not fit into slot, are truncated.
+//!
+//!Ring is lock-free and does not allocate, so it can be used without `std` (e.g. to include recent logs into crash report).
+//!
+//!Global ring of [SLOTS](constant.SLOTS.html) slots, [SLOT_SIZE](constant.SLOT_SIZE.html) bytes each, is registered as
+//![sink](../sink/index.html) by [set_level](../fn.set_level.html) and can be read with [dump](fn.dump.html) or [iter](fn.iter.html).
+//!Its size is set for whole build by `ROGU_RING_SLOTS` (64 by default) and `ROGU_RING_SLOT_SIZE` (128 by default) environment variables,
+//!e.g. `ROGU_RING_SLOTS=256 ROGU_RING_SLOT_SIZE=256` keeps last 256 records, up to 64 KiB in total.
+//!Formatted record is truncated to slot size on char boundary, so slot size should fit the longest record to keep.
+//!
+//!Custom rings can be created as `static` and registered with [sink::add](../sink/fn.add.html).
+
+use crate::Level;
+use crate::sink::{self, Sink};
+
+use core::{cmp, fmt};
+use core::sync::atomic::{fence, AtomicBool, AtomicU8, AtomicUsize, Ordering};
+
+include!(concat!(env!("OUT_DIR"), "/ring_size.rs"));
+
+///Number of slots in global ring, set by `ROGU_RING_SLOTS`.
+pub const SLOTS: usize = RING_SLOTS;
+///Size of slot in global ring in bytes, set by `ROGU_RING_SLOT_SIZE`.
+pub const SLOT_SIZE: usize = RING_SLOT_SIZE;
+
+static GLOBAL: Ring<SLOTS, SLOT_SIZE> = Ring::new();
+static IS_INIT: AtomicBool = AtomicBool::new(false);
+
+struct Slot<const SIZE: usize> {
+    //Sequence lock: `2 * index + 1` while record is written, `2 * index + 2` once done.
+    //Writer claims slot only if it holds older finished record, so writers never overlap.
+    //Empty slot is `0`, which is older than any record and never matches one.
+    seq: AtomicUsize,
+    level: AtomicU8,
+    len: AtomicUsize,
+    text: [AtomicU8; SIZE],
+}
+
+impl<const SIZE: usize> Slot<SIZE> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const BYTE: AtomicU8 = AtomicU8::new(0);
+    #[allow(clippy::declare_interior_mutable_const)]
+    const EMPTY: Self = Self {
+        seq: AtomicUsize::new(0),
+        level: AtomicU8::new(0),
+        len: AtomicUsize::new(0),
+        text: [Self::BYTE; SIZE],
+    };
+}
+
+struct SlotWriter<'a, const SIZE: usize> {
+    slot: &'a Slot<SIZE>,
+    len: usize,
+    is_truncated: bool,
+}
+
+impl<const SIZE: usize> fmt::Write for SlotWriter<'_, SIZE> {
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        if self.is_truncated {
+            return Ok(());
+        }
+
+        let mut write_len = cmp::min(SIZE - self.len, text.len());
+        while !text.is_char_boundary(write_len) {
+            write_len -= 1;
+        }
+        self.is_truncated = write_len < text.len();
+
+        for (dest, byte) in self.slot.text[self.len..].iter().zip(&text.as_bytes()[..write_len]) {
+            dest.store(*byte, Ordering::Relaxed);
+        }
+        self.len += write_len;
+
+        Ok(())
+    }
+}
+
+///Ring buffer of `SLOTS` records, each up to `SIZE` bytes.
+pub struct Ring<const SLOTS: usize, const SIZE: usize> {
+    head: AtomicUsize,
+    slots: [Slot<SIZE>; SLOTS],
+}
+
+impl<const SLOTS: usize, const SIZE: usize> Ring<SLOTS, SIZE> {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const EMPTY_SLOT: Slot<SIZE> = Slot::EMPTY;
+
+    ///Creates empty ring.
+    pub const fn new() -> Self {
+        Self {
+            head: AtomicUsize::new(0),
+            slots: [Self::EMPTY_SLOT; SLOTS],
+        }
+    }
+
+    ///Returns iterator over records, from oldest to newest.
+    ///
+    ///Records, that are being written or overwritten during iteration, are skipped.
+    ///Record is also missing, if it was dropped because its slot was still written by lapped writer.
+    pub fn iter(&self) -> Iter<'_, SLOTS, SIZE> {
+        let head = self.head.load(Ordering::Acquire);
+        Iter {
+            ring: self,
+            //Until ring is filled, only written slots are visited
+            index: head.saturating_sub(SLOTS),
+            end: head,
+        }
+    }
+
+    ///Writes records, from oldest to newest, one per line.
+    pub fn dump<W: fmt::Write>(&self, out: &mut W) -> fmt::Result {
+        for entry in self.iter() {
+            out.write_str(entry.text())?;
+            out.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<const SLOTS: usize, const SIZE: usize> Default for Ring<SLOTS, SIZE> {
+    #[inline(always)]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const SLOTS: usize, const SIZE: usize> Sink for Ring<SLOTS, SIZE> {
+    fn write(&self, record: &sink::Record<'_>) {
+        let index = self.head.fetch_add(1, Ordering::Relaxed);
+        let slot = &self.slots[index % SLOTS];
+        let seq = index.wrapping_mul(2).wrapping_add(1);
+
+        //Slot is still written by other writer or already holds newer record, which happens only if ring is lapped.
+        //Record is dropped then, as neither writer can wait for other.
+        let current = slot.seq.load(Ordering::Relaxed);
+        if current % 2 == 1 || (seq.wrapping_sub(current) as isize) < 0 {
+            return;
+        }
+        if slot.seq.compare_exchange(current, seq, Ordering::Acquire, Ordering::Relaxed).is_err() {
+            return;
+        }
+        fence(Ordering::Release);
+
+        let mut writer = SlotWriter {
+            slot,
+            len: 0,
+            is_truncated: false,
+        };
+        let _ = fmt::write(&mut writer, format_args!("{:<5} - [{}:{}] - {}", crate::data::level::name(record.level()), record.file(), record.line(), record.args()));
+
+        slot.level.store(record.level() as u8, Ordering::Relaxed);
+        slot.len.store(writer.len, Ordering::Relaxed);
+        slot.seq.store(seq.wrapping_add(1), Ordering::Release);
+    }
+}
+
+///Copy of record, stored in ring.
+pub struct Entry<const SIZE: usize> {
+    level: Level,
+    len: usize,
+    text: [u8; SIZE],
+}
+
+impl<const SIZE: usize> Entry<SIZE> {
+    #[inline(always)]
+    ///Record's level.
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    #[inline]
+    ///Formatted record, without trailing newline.
+    pub fn text(&self) -> &str {
+        let text = &self.text[..self.len];
+        match core::str::from_utf8(text) {
+            Ok(text) => text,
+            //Should not happen, as record is truncated on char boundary
+            Err(error) => unsafe {
+                core::str::from_utf8_unchecked(&text[..error.valid_up_to()])
+            },
+        }
+    }
+}
+
+///Iterator over ring's records.
+pub struct Iter<'a, const SLOTS: usize, const SIZE: usize> {
+    ring: &'a Ring<SLOTS, SIZE>,
+    index: usize,
+    end: usize,
+}
+
+impl<const SLOTS: usize, const SIZE: usize> Iterator for Iter<'_, SLOTS, SIZE> {
+    type Item = Entry<SIZE>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.index != self.end {
+            let index = self.index;
+            self.index = self.index.wrapping_add(1);
+
+            let slot = &self.ring.slots[index % SLOTS];
+            let seq = slot.seq.load(Ordering::Acquire);
+            if seq != index.wrapping_mul(2).wrapping_add(2) {
+                continue;
+            }
+
+            let mut entry = Entry {
+                level: Level::from_raw(slot.level.load(Ordering::Relaxed)),
+                len: cmp::min(slot.len.load(Ordering::Relaxed), SIZE),
+                text: [0; SIZE],
+            };
+            for (dest, byte) in entry.text[..entry.len].iter_mut().zip(slot.text.iter()) {
+                *dest = byte.load(Ordering::Relaxed);
+            }
+
+            fence(Ordering::Acquire);
+            if slot.seq.load(Ordering::Relaxed) == seq {
+                return Some(entry);
+            }
+        }
+
+        None
+    }
+}
+
+///Registers global ring as sink, if it is not registered yet.
+///
+///Called by [set_level](../fn.set_level.html).
+pub fn init() {
+    if !IS_INIT.swap(true, Ordering::AcqRel) {
+        sink::add(&GLOBAL);
+    }
+}
+
+#[inline]
+///Returns iterator over records of global ring, from oldest to newest.
+pub fn iter() -> Iter<'static, SLOTS, SLOT_SIZE> {
+    GLOBAL.iter()
+}
+
+#[inline]
+///Writes records of global ring, from oldest to newest, one per line.
+pub fn dump<W: fmt::Write>(out: &mut W) -> fmt::Result {
+    GLOBAL.dump(out)
+}
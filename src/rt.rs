@@ -10,4 +10,7 @@ pub fn init() {
             SetConsoleOutputCP(65001);
         }
     }
+
+    #[cfg(all(feature = "ring", not(feature = "ufmt")))]
+    crate::ring::init();
 }
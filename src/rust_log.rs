@@ -1,12 +1,14 @@
-#[cfg(feature = "ufmt")]
-compile_error!("Unable to use ufmt writers with log feature");
+#[cfg(all(feature = "ufmt", feature = "log_facade"))]
+compile_error!("Unable to use ufmt macros with log_facade feature");
+
+use core::fmt;
 
 pub struct Logger;
 
-impl Into<crate::Level> for log::Level {
+impl From<log::Level> for crate::Level {
     #[inline(always)]
-    fn into(self) -> crate::Level {
-        match self {
+    fn from(level: log::Level) -> Self {
+        match level {
             log::Level::Error => crate::Level::ERROR,
             log::Level::Warn => crate::Level::WARN,
             log::Level::Info => crate::Level::INFO,
@@ -16,13 +18,17 @@ impl Into<crate::Level> for log::Level {
     }
 }
 
-impl Into<log::LevelFilter> for crate::Level {
+impl From<crate::Level> for log::LevelFilter {
     #[inline(always)]
-    fn into(self) -> log::LevelFilter {
-        match self {
+    fn from(level: crate::Level) -> Self {
+        match level {
             crate::Level::NONE => log::LevelFilter::Off,
-            crate::Level::ERROR => log::LevelFilter::Error,
+            crate::Level::FATAL | crate::Level::ERROR => log::LevelFilter::Error,
+            #[cfg(feature = "extended_levels")]
+            crate::Level::CRITICAL => log::LevelFilter::Error,
             crate::Level::WARN => log::LevelFilter::Warn,
+            #[cfg(feature = "extended_levels")]
+            crate::Level::NOTICE => log::LevelFilter::Info,
             crate::Level::INFO => log::LevelFilter::Info,
             crate::Level::DEBUG => log::LevelFilter::Debug,
             crate::Level::TRACE => log::LevelFilter::Trace,
@@ -30,32 +36,103 @@ impl Into<log::LevelFilter> for crate::Level {
     }
 }
 
+//Key-value, carrying rogu level, that has no exact `log` equivalent (e.g. `FATAL`).
+const LEVEL_KEY: &str = "level";
+
+//Returns level of record, restoring rogu level from `LEVEL_KEY`, if it is set.
+fn record_level(record: &log::Record) -> (crate::Level, bool) {
+    let level = record.key_values().get(log::kv::Key::from_str(LEVEL_KEY)).and_then(|value| value.to_borrowed_str().and_then(|value| value.parse::<crate::Level>().ok()));
+    match level {
+        Some(level) if log::Level::from(level) == record.level() => (level, true),
+        _ => (record.level().into(), false),
+    }
+}
+
+//Renders key-values as ` key=value`, skipping restored level
+struct KeyValues<'a>(&'a dyn log::kv::Source, bool);
+
+struct KeyValueWriter<'a, 'b>(&'a mut fmt::Formatter<'b>, bool);
+
+impl<'kvs> log::kv::VisitSource<'kvs> for KeyValueWriter<'_, '_> {
+    #[inline]
+    fn visit_pair(&mut self, key: log::kv::Key<'kvs>, value: log::kv::Value<'kvs>) -> Result<(), log::kv::Error> {
+        if !(self.1 && key.as_str() == LEVEL_KEY) {
+            write!(self.0, " {}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for KeyValues<'_> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.visit(&mut KeyValueWriter(fmt, self.1)).map_err(|_| fmt::Error)
+    }
+}
+
+//Renders `target` or `target (module)`, if record comes from different module
+struct Target<'a>(&'a log::Record<'a>);
+
+impl fmt::Display for Target<'_> {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.module_path() {
+            Some(module) if module != self.0.target() => write!(fmt, "{} ({})", self.0.target(), module),
+            _ => fmt.write_str(self.0.target()),
+        }
+    }
+}
+
+//Adapts ufmt writer to render `log::Record` arguments
+#[cfg(feature = "ufmt")]
+struct UfmtWriter<'a, W: ufmt::uWrite>(&'a mut W);
+
+#[cfg(feature = "ufmt")]
+impl<W: ufmt::uWrite> fmt::Write for UfmtWriter<'_, W> {
+    #[inline(always)]
+    fn write_str(&mut self, text: &str) -> fmt::Result {
+        self.0.write_str(text).map_err(|_| fmt::Error)
+    }
+}
+
 impl log::Log for Logger {
     #[inline(always)]
     fn enabled(&self, meta: &log::Metadata) -> bool {
-        crate::is_enabled(meta.level().into())
+        crate::is_target_enabled(meta.target(), meta.level().into())
     }
 
     #[inline]
     fn log(&self, record: &log::Record) {
-        let level = record.level().into();
-
-        if crate::is_enabled(level) {
-            let mut out = match level {
-                crate::Level::ERROR => crate::Out::error(""),
-                crate::Level::WARN => crate::Out::warn(""),
-                crate::Level::INFO => crate::Out::info(""),
-                crate::Level::DEBUG => crate::Out::debug(""),
-                crate::Level::TRACE => crate::Out::trace(""),
-                _ => panic!("Unexpected log level"),
-            };
+        let (level, is_restored) = record_level(record);
+
+        if crate::is_target_enabled(record.target(), level) {
+            let target = Target(record);
+            let kv = KeyValues(record.key_values(), is_restored);
 
             #[cfg(not(feature = "ufmt"))]
             {
                 use core::fmt::Write;
+                if crate::out::is_direct() {
+                    let mut out = crate::out::by_level(level, "");
+                    let _ = match (record.file(), record.line()) {
+                        (Some(file), Some(line)) => core::writeln!(out, "- [{}:{}] - {}: {}{}", crate::out::file_name(file), line, target, record.args(), kv),
+                        _ => core::writeln!(out, "{}: {}{}", target, record.args(), kv),
+                    };
+                }
+
+                if crate::sink::is_active() {
+                    crate::sink::write(&crate::sink::Record::new(level, record.file().unwrap_or(""), record.line().unwrap_or(0), record.module_path().unwrap_or(""), format_args!("{}{}", record.args(), kv)).with_target(record.target()));
+                }
+            }
+
+            #[cfg(feature = "ufmt")]
+            {
+                use core::fmt::Write;
+                let mut out = crate::out::by_level(level, "");
+                let mut out = UfmtWriter(&mut out);
                 let _ = match (record.file(), record.line()) {
-                    (Some(file), Some(line)) => core::writeln!(out, "- [{}:{}] - {}", file, line, record.args()),
-                    _ => core::writeln!(out, "{}", record.args()),
+                    (Some(file), Some(line)) => core::writeln!(out, "- [{}:{}] - {}: {}{}", crate::out::file_name(file), line, target, record.args(), kv),
+                    _ => core::writeln!(out, "{}: {}{}", target, record.args(), kv),
                 };
             }
         }
@@ -66,8 +143,76 @@ impl log::Log for Logger {
     }
 }
 
+impl From<crate::Level> for log::Level {
+    #[inline(always)]
+    fn from(level: crate::Level) -> Self {
+        match level {
+            crate::Level::FATAL | crate::Level::ERROR => log::Level::Error,
+            #[cfg(feature = "extended_levels")]
+            crate::Level::CRITICAL => log::Level::Error,
+            crate::Level::WARN => log::Level::Warn,
+            #[cfg(feature = "extended_levels")]
+            crate::Level::NOTICE => log::Level::Info,
+            crate::Level::INFO => log::Level::Info,
+            crate::Level::DEBUG => log::Level::Debug,
+            _ => log::Level::Trace,
+        }
+    }
+}
+
+#[cfg(feature = "log_facade")]
+#[inline(always)]
+///Checks `log` max level, which is set by whichever logger is installed.
+///
+///`FATAL` is always enabled.
+pub fn enabled(level: crate::Level) -> bool {
+    match level {
+        crate::Level::FATAL => true,
+        level => crate::is_static_enabled(level) && log::Level::from(level) <= log::max_level(),
+    }
+}
+
+#[cfg(feature = "log_facade")]
+///Passes record to installed `log::Log`.
+///
+///If it is `Logger`, record is written to `Out` directly, so there is no way back into rogu macros.
+///
+///Level without exact `log` equivalent is passed as `level` key-value (e.g. `level="FATAL"` with `log::Level::Error`),
+///but installed logger may still filter it out by `log` level.
+pub fn forward(level: crate::Level, file: &'static str, line: u32, module: &'static str, target: &str, args: fmt::Arguments<'_>) {
+    let name = crate::data::level::name(level);
+    let key_values = (LEVEL_KEY, name);
+    let is_exact = match level {
+        crate::Level::FATAL => false,
+        #[cfg(feature = "extended_levels")]
+        crate::Level::CRITICAL | crate::Level::NOTICE => false,
+        _ => true,
+    };
+
+    let mut record = log::Record::builder();
+    record.level(level.into())
+          .target(target)
+          .module_path_static(Some(module))
+          .file_static(Some(file))
+          .line(Some(line))
+          .args(args);
+    if !is_exact {
+        record.key_values(&key_values);
+    }
+    log::logger().log(&record.build());
+}
+
+static IS_INSTALLED: core::sync::atomic::AtomicBool = core::sync::atomic::AtomicBool::new(false);
+
+///Installs rogu's logger, if no other is installed, and sets max level of `log` only then.
+///
+///Max level of other logger belongs to its owner.
 pub fn init(level: log::LevelFilter) {
+    use core::sync::atomic::Ordering;
     static LOGGER: Logger = Logger;
-    log::set_max_level(level);
-    let _ = log::set_logger(&LOGGER);
+
+    if IS_INSTALLED.load(Ordering::Acquire) || log::set_logger(&LOGGER).is_ok() {
+        IS_INSTALLED.store(true, Ordering::Release);
+        log::set_max_level(level);
+    }
 }
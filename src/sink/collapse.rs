@@ -0,0 +1,289 @@
+//!Collapsing of consecutive duplicate records.
+//!
+//![Collapse](struct.Collapse.html) wraps sink, passing first of consecutive identical records (same level, callsite and message) through,
+//!while suppressing its repetitions.
+//!Number of suppressed records is written as `last message repeated N times` from the same callsite, once:
+//!
+//!- Different record arrives;
+//!- Repetition arrives after timeout since last summary (or original record);
+//!- [tick](struct.Collapse.html#method.tick) is called after timeout, e.g. by thread of [start_timer](struct.Collapse.html#method.start_timer);
+//!- Sink is flushed;
+//!
+//!Without timer, summary of the last repetitions waits for one of the other events.
+//!Summary and record, that caused it, are written together, so records of other threads cannot get between them.
+//!
+//!Platform logger (e.g. stdout, which is collected into journal) writes every record by itself,
+//!unless it is replaced by [Platform](struct.Platform.html) sink wrapped into adapter with [set_platform](fn.set_platform.html).
+//!
+//!```rust
+//!use rogu::sink::{self, Record, Sink};
+//!use rogu::sink::collapse::Collapse;
+//!
+//!use core::time::Duration;
+//!
+//!struct Journal;
+//!
+//!impl Sink for Journal {
+//!    fn write(&self, record: &Record<'_>) {
+//!        println!("{}", record.args());
+//!    }
+//!}
+//!
+//!static JOURNAL: Collapse<Journal> = Collapse::new(Journal, Duration::from_secs(30));
+//!sink::add(&JOURNAL);
+//!JOURNAL.start_timer().expect("to spawn timer");
+//!
+//!static PLATFORM: Collapse<sink::collapse::Platform> = Collapse::new(sink::collapse::Platform, Duration::from_secs(30));
+//!sink::collapse::set_platform(&PLATFORM);
+//!```
+
+use super::{Record, Sink};
+use crate::Level;
+
+use core::cell::Cell;
+use core::fmt::Write;
+use core::time::Duration;
+use std::io;
+use std::string::{String, ToString};
+use std::sync::{Mutex, MutexGuard};
+use std::thread;
+
+struct Last {
+    level: Level,
+    file: String,
+    line: u32,
+    module: String,
+    target: String,
+    message: String,
+    //Time of original record or last summary.
+    since: Duration,
+    repeated: usize,
+}
+
+impl Last {
+    fn is_same(&self, record: &Record<'_>, message: &str) -> bool {
+        self.level == record.level() && self.line == record.line() && self.file == record.file() && self.module == record.module() && self.target == record.target() && self.message == message
+    }
+
+    //Takes summary of repetitions, keeping record to compare with.
+    fn take_summary(&mut self) -> Option<Summary> {
+        match self.repeated {
+            0 => None,
+            repeated => {
+                self.repeated = 0;
+                Some(Summary {
+                    level: self.level,
+                    file: self.file.clone(),
+                    line: self.line,
+                    module: self.module.clone(),
+                    target: self.target.clone(),
+                    repeated,
+                })
+            }
+        }
+    }
+
+    fn into_summary(self) -> Option<Summary> {
+        match self.repeated {
+            0 => None,
+            repeated => Some(Summary {
+                level: self.level,
+                file: self.file,
+                line: self.line,
+                module: self.module,
+                target: self.target,
+                repeated,
+            }),
+        }
+    }
+}
+
+std::thread_local! {
+    //Adapter, that writes into wrapped sink on current thread.
+    static WRITING: Cell<usize> = const { Cell::new(0) };
+}
+
+//Marks adapter as writing on current thread, so that records of wrapped sink are passed through instead of waiting for lock.
+struct Writing(usize);
+
+impl Writing {
+    fn enter<S>(adapter: &Collapse<S>) -> Self {
+        let adapter = adapter as *const Collapse<S> as usize;
+        Self(WRITING.with(|writing| writing.replace(adapter)))
+    }
+
+    fn is_active<S>(adapter: &Collapse<S>) -> bool {
+        WRITING.try_with(|writing| writing.get() == adapter as *const Collapse<S> as usize).unwrap_or(false)
+    }
+}
+
+impl Drop for Writing {
+    fn drop(&mut self) {
+        let _ = WRITING.try_with(|writing| writing.set(self.0));
+    }
+}
+
+struct Summary {
+    level: Level,
+    file: String,
+    line: u32,
+    module: String,
+    target: String,
+    repeated: usize,
+}
+
+impl Summary {
+    fn write<S: Sink>(&self, sink: &S) {
+        sink.write(&Record::new(self.level, &self.file, self.line, &self.module, format_args!("last message repeated {} times", self.repeated)).with_target(&self.target));
+    }
+}
+
+///Sink adapter, collapsing consecutive duplicate records.
+pub struct Collapse<S> {
+    inner: S,
+    timeout: Duration,
+    last: Mutex<Option<Last>>,
+}
+
+impl<S: Sink> Collapse<S> {
+    ///Creates new adapter, writing summary of repetitions at least every `timeout`.
+    pub const fn new(inner: S, timeout: Duration) -> Self {
+        Self {
+            inner,
+            timeout,
+            last: Mutex::new(None),
+        }
+    }
+
+    #[inline(always)]
+    ///Returns wrapped sink.
+    pub fn inner(&self) -> &S {
+        &self.inner
+    }
+
+    #[inline]
+    fn lock(&self) -> MutexGuard<'_, Option<Last>> {
+        match self.last.lock() {
+            Ok(last) => last,
+            Err(error) => error.into_inner(),
+        }
+    }
+
+    ///Writes summary of repetitions, if timeout passed since original record or last summary.
+    pub fn tick(&self) {
+        let now = crate::clock::monotonic();
+        let mut last = self.lock();
+        let summary = match last.as_mut() {
+            Some(last) if last.repeated > 0 && now.saturating_sub(last.since) >= self.timeout => {
+                last.since = now;
+                last.take_summary()
+            },
+            _ => None,
+        };
+
+        if let Some(summary) = summary {
+            let _writing = Writing::enter(self);
+            summary.write(&self.inner);
+        }
+    }
+}
+
+impl<S: Sink + 'static> Collapse<S> {
+    ///Spawns thread, calling [tick](#method.tick) every `timeout`, so that summary is written even if no other record arrives.
+    pub fn start_timer(&'static self) -> io::Result<()> {
+        thread::Builder::new().name("rogu-collapse".to_string()).spawn(move || loop {
+            thread::sleep(self.timeout);
+            self.tick();
+        })?;
+        Ok(())
+    }
+}
+
+impl<S: Sink> Sink for Collapse<S> {
+    fn write(&self, record: &Record<'_>) {
+        //Written by wrapped sink, while lock is held by this thread
+        if Writing::is_active(self) {
+            self.inner.write(record);
+            return;
+        }
+
+        let mut message = String::new();
+        let _ = write!(message, "{}", record.args());
+        let now = crate::clock::monotonic();
+
+        //Lock is held until summary and record are written
+        let mut last = self.lock();
+        let (summary, is_new) = match last.as_mut() {
+            Some(last) if last.is_same(record, &message) => {
+                last.repeated += 1;
+                match now.saturating_sub(last.since) >= self.timeout {
+                    true => {
+                        last.since = now;
+                        (last.take_summary(), false)
+                    },
+                    false => (None, false),
+                }
+            },
+            _ => {
+                let previous = last.replace(Last {
+                    level: record.level(),
+                    file: record.file().into(),
+                    line: record.line(),
+                    module: record.module().into(),
+                    target: record.target().into(),
+                    message,
+                    since: now,
+                    repeated: 0,
+                });
+                (previous.and_then(Last::into_summary), true)
+            },
+        };
+
+        let _writing = Writing::enter(self);
+        if let Some(summary) = summary {
+            summary.write(&self.inner);
+        }
+        if is_new {
+            self.inner.write(record);
+        }
+    }
+
+    fn flush(&self) {
+        {
+            let mut last = self.lock();
+            if let Some(summary) = last.as_mut().and_then(Last::take_summary) {
+                let _writing = Writing::enter(self);
+                summary.write(&self.inner);
+            }
+        }
+
+        self.inner.flush();
+    }
+}
+
+///Sink, that writes records to platform logger the same way as macros do.
+///
+///Target is written only if it differs from module.
+pub struct Platform;
+
+impl Sink for Platform {
+    fn write(&self, record: &Record<'_>) {
+        let mut out = crate::out::by_level(record.level(), "");
+        let file = crate::out::file_name(record.file());
+        let _ = match record.target() == record.module() {
+            true => writeln!(out, "- [{}:{}] - {}{}", file, record.line(), crate::context::Prefix, record.args()),
+            false => writeln!(out, "- [{}:{}] - {}{}: {}", file, record.line(), crate::context::Prefix, record.target(), record.args()),
+        };
+    }
+}
+
+///Registers `sink` and stops macros from writing to platform logger directly, so that it is written only through `sink`.
+///
+///Returns `false`, leaving platform logger as it is, if there are already `MAX_SINKS` registered.
+pub fn set_platform(sink: &'static Collapse<Platform>) -> bool {
+    let is_added = super::add(sink);
+    if is_added {
+        crate::out::set_direct(false);
+    }
+    is_added
+}
@@ -0,0 +1,552 @@
+//!Batched HTTP push sink.
+//!
+//!Records are handed over to background thread, which groups them into batches and `POST`s them
+//!as JSON to configured endpoint.
+//!
+//!Supported payloads:
+//!
+//!- [Loki push API](https://grafana.com/docs/loki/latest/reference/loki-http-api/#ingest-logs);
+//!- [OTLP/HTTP](https://opentelemetry.io/docs/specs/otlp/#otlphttp) JSON logs;
+//!
+//!Both carry source location, module, target and [context](../../context/index.html) fields of record as attributes,
+//!which are written as structured metadata in Loki.
+//!Loki streams are additionally labeled by `level` and `target`.
+//!
+//!Only plain `http` is supported, use local collector or proxy for TLS.
+//!
+//!Logging thread never waits for network: when queue is full, record is dropped and counted in
+//![HttpSink::dropped](struct.HttpSink.html#method.dropped).
+//!Batches that failed to be delivered after all retries are kept in bounded spill buffer and sent
+//!together with next batch.
+
+use crate::Level;
+use super::{json, Entry, Record, Sink};
+
+use std::io::{self, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::string::{String, ToString};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, SyncSender, Receiver, RecvTimeoutError, TrySendError};
+use std::time::{Duration, Instant};
+use std::vec::Vec;
+use std::thread;
+use core::fmt::Write as FmtWrite;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+///Payload format
+pub enum Format {
+    ///Loki push API: `{"streams":[{"stream":{"level":..,"target":..},"values":[[ts, line, {attributes}]]}]}`
+    Loki,
+    ///OTLP/HTTP JSON logs: `{"resourceLogs":[..]}`
+    Otlp,
+}
+
+///HTTP sink configuration.
+pub struct Config {
+    url: String,
+    format: Format,
+    labels: Vec<(String, String)>,
+    max_batch_size: usize,
+    max_batch_age: Duration,
+    queue_capacity: usize,
+    spill_capacity: usize,
+    max_retries: u32,
+    backoff: Duration,
+    timeout: Duration,
+}
+
+impl Config {
+    ///Creates new config for `url` in `format`.
+    ///
+    ///Url must be in form `http://host[:port][/path]`.
+    pub fn new(url: &str, format: Format) -> Self {
+        Self {
+            url: url.to_string(),
+            format,
+            labels: Vec::new(),
+            max_batch_size: 100,
+            max_batch_age: Duration::from_secs(1),
+            queue_capacity: 1024,
+            spill_capacity: 4096,
+            max_retries: 3,
+            backoff: Duration::from_millis(100),
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    #[inline(always)]
+    ///Creates config for Loki push API (e.g. `http://localhost:3100/loki/api/v1/push`).
+    pub fn loki(url: &str) -> Self {
+        Self::new(url, Format::Loki)
+    }
+
+    #[inline(always)]
+    ///Creates config for OTLP/HTTP logs endpoint (e.g. `http://localhost:4318/v1/logs`).
+    pub fn otlp(url: &str) -> Self {
+        Self::new(url, Format::Otlp)
+    }
+
+    ///Adds label, which is used as Loki stream label or OTLP resource attribute.
+    pub fn label(mut self, key: &str, value: &str) -> Self {
+        self.labels.push((key.to_string(), value.to_string()));
+        self
+    }
+
+    ///Sets maximum number of records in single request.
+    ///
+    ///Defaults to 100.
+    pub fn max_batch_size(mut self, size: usize) -> Self {
+        self.max_batch_size = core::cmp::max(size, 1);
+        self
+    }
+
+    ///Sets maximum time record waits in batch before being sent.
+    ///
+    ///Defaults to 1 second.
+    pub fn max_batch_age(mut self, age: Duration) -> Self {
+        self.max_batch_age = age;
+        self
+    }
+
+    ///Sets number of records that can be queued for background thread.
+    ///
+    ///Defaults to 1024.
+    pub fn queue_capacity(mut self, capacity: usize) -> Self {
+        self.queue_capacity = core::cmp::max(capacity, 1);
+        self
+    }
+
+    ///Sets maximum number of undelivered records to keep for next attempt.
+    ///
+    ///Defaults to 4096.
+    pub fn spill_capacity(mut self, capacity: usize) -> Self {
+        self.spill_capacity = capacity;
+        self
+    }
+
+    ///Sets number of retries and initial backoff, which is doubled on each retry.
+    ///
+    ///Defaults to 3 retries starting with 100ms.
+    pub fn retry(mut self, max_retries: u32, backoff: Duration) -> Self {
+        self.max_retries = max_retries;
+        self.backoff = backoff;
+        self
+    }
+
+    ///Sets connect and IO timeout of single request.
+    ///
+    ///Defaults to 5 seconds.
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+}
+
+enum Message {
+    Record(Entry),
+    Flush(mpsc::Sender<()>),
+}
+
+struct Endpoint {
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl Endpoint {
+    fn parse(url: &str) -> io::Result<Self> {
+        let rest = match url.strip_prefix("http://") {
+            Some(rest) => rest,
+            None => return Err(io::Error::new(io::ErrorKind::InvalidInput, "Only http:// urls are supported")),
+        };
+
+        let (authority, path) = match rest.find('/') {
+            Some(idx) => (&rest[..idx], &rest[idx..]),
+            None => (rest, "/"),
+        };
+
+        let (host, port) = match authority.rfind(':') {
+            Some(idx) if !authority.ends_with(']') => match authority[idx+1..].parse() {
+                Ok(port) => (&authority[..idx], port),
+                Err(_) => return Err(io::Error::new(io::ErrorKind::InvalidInput, "Invalid port")),
+            },
+            _ => (authority, 80),
+        };
+
+        if host.is_empty() {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "Missing host"));
+        }
+
+        Ok(Self {
+            host: host.to_string(),
+            port,
+            path: path.to_string(),
+        })
+    }
+}
+
+enum PostError {
+    Retry,
+    Reject,
+}
+
+struct Worker {
+    endpoint: Endpoint,
+    config: Config,
+    dropped: Arc<AtomicUsize>,
+    spill: VecDeque<Entry>,
+}
+
+impl Worker {
+    fn run(mut self, receiver: Receiver<Message>) {
+        let mut batch = Vec::new();
+        let mut batch_start = Instant::now();
+
+        loop {
+            let timeout = match batch.is_empty() && self.spill.is_empty() {
+                true => Duration::from_secs(3600),
+                false => self.config.max_batch_age.saturating_sub(batch_start.elapsed()),
+            };
+
+            match receiver.recv_timeout(timeout) {
+                Ok(Message::Record(entry)) => {
+                    if batch.is_empty() {
+                        batch_start = Instant::now();
+                    }
+                    batch.push(entry);
+                    if batch.len() >= self.config.max_batch_size {
+                        self.deliver(&mut batch);
+                    }
+                },
+                Ok(Message::Flush(ack)) => {
+                    //Pick up everything that is queued before flush request.
+                    let mut acks = std::vec![ack];
+                    while let Ok(message) = receiver.try_recv() {
+                        match message {
+                            Message::Record(entry) => batch.push(entry),
+                            Message::Flush(ack) => acks.push(ack),
+                        }
+                    }
+                    self.deliver(&mut batch);
+                    for ack in acks {
+                        let _ = ack.send(());
+                    }
+                },
+                Err(RecvTimeoutError::Timeout) => {
+                    self.deliver(&mut batch);
+                    batch_start = Instant::now();
+                },
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.deliver(&mut batch);
+                    break;
+                },
+            }
+        }
+    }
+
+    fn deliver(&mut self, batch: &mut Vec<Entry>) {
+        self.spill.extend(batch.drain(..));
+
+        while !self.spill.is_empty() {
+            let len = core::cmp::min(self.spill.len(), self.config.max_batch_size);
+            let body = match self.config.format {
+                Format::Loki => loki_body(&self.config.labels, self.spill.range(..len)),
+                Format::Otlp => otlp_body(&self.config.labels, self.spill.range(..len)),
+            };
+
+            match self.post_with_retry(&body) {
+                Ok(()) => {
+                    self.spill.drain(..len);
+                },
+                Err(PostError::Reject) => {
+                    self.dropped.fetch_add(len, Ordering::Relaxed);
+                    self.spill.drain(..len);
+                },
+                Err(PostError::Retry) => break,
+            }
+        }
+
+        if self.spill.len() > self.config.spill_capacity {
+            let excess = self.spill.len() - self.config.spill_capacity;
+            self.dropped.fetch_add(excess, Ordering::Relaxed);
+            self.spill.drain(..excess);
+        }
+    }
+
+    fn post_with_retry(&self, body: &str) -> Result<(), PostError> {
+        let mut backoff = self.config.backoff;
+        let mut attempt = 0;
+
+        loop {
+            match self.post(body) {
+                Ok(code) if (200..300).contains(&code) => return Ok(()),
+                Ok(code) if code != 429 && code < 500 => return Err(PostError::Reject),
+                _ => (),
+            }
+
+            if attempt >= self.config.max_retries {
+                return Err(PostError::Retry);
+            }
+
+            attempt += 1;
+            thread::sleep(backoff);
+            backoff = backoff.saturating_mul(2);
+        }
+    }
+
+    fn post(&self, body: &str) -> io::Result<u16> {
+        let endpoint = &self.endpoint;
+        let addr = match (endpoint.host.as_str(), endpoint.port).to_socket_addrs()?.next() {
+            Some(addr) => addr,
+            None => return Err(io::Error::new(io::ErrorKind::NotFound, "Unable to resolve host")),
+        };
+
+        let mut stream = TcpStream::connect_timeout(&addr, self.config.timeout)?;
+        stream.set_read_timeout(Some(self.config.timeout))?;
+        stream.set_write_timeout(Some(self.config.timeout))?;
+
+        write!(stream, "POST {} HTTP/1.1\r\nHost: {}:{}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", endpoint.path, endpoint.host, endpoint.port, body.len())?;
+        stream.write_all(body.as_bytes())?;
+        stream.flush()?;
+
+        //Only status line is of interest.
+        let mut response = [0u8; 32];
+        let mut len = 0;
+        while len < response.len() {
+            match stream.read(&mut response[len..])? {
+                0 => break,
+                read => len += read,
+            }
+        }
+
+        let status = core::str::from_utf8(&response[..len]).ok().and_then(|line| line.split(' ').nth(1)).and_then(|code| code.parse().ok());
+        match status {
+            Some(code) => Ok(code),
+            None => Err(io::Error::new(io::ErrorKind::InvalidData, "Invalid HTTP response")),
+        }
+    }
+}
+
+const fn level_name(level: Level) -> &'static str {
+    match level {
+        Level::FATAL => "fatal",
+        #[cfg(feature = "extended_levels")]
+        Level::CRITICAL => "critical",
+        Level::ERROR => "error",
+        Level::WARN => "warn",
+        #[cfg(feature = "extended_levels")]
+        Level::NOTICE => "notice",
+        Level::INFO => "info",
+        Level::DEBUG => "debug",
+        _ => "trace",
+    }
+}
+
+const fn severity_number(level: Level) -> u8 {
+    match level {
+        Level::FATAL => 21,
+        #[cfg(feature = "extended_levels")]
+        Level::CRITICAL => 20,
+        Level::ERROR => 17,
+        Level::WARN => 13,
+        #[cfg(feature = "extended_levels")]
+        Level::NOTICE => 10,
+        Level::INFO => 9,
+        Level::DEBUG => 5,
+        _ => 1,
+    }
+}
+
+fn loki_body<'a>(labels: &[(String, String)], entries: impl Iterator<Item = &'a Entry> + Clone) -> String {
+    let mut body = String::from("{\"streams\":[");
+
+    let mut first_stream = true;
+    for level in [Level::FATAL, #[cfg(feature = "extended_levels")] Level::CRITICAL, Level::ERROR, Level::WARN, #[cfg(feature = "extended_levels")] Level::NOTICE, Level::INFO, Level::DEBUG, Level::TRACE].iter() {
+        //Stream per target, in order of first record
+        let mut targets = Vec::<&str>::new();
+        for entry in entries.clone().filter(|entry| entry.level == *level) {
+            if !targets.contains(&entry.target.as_str()) {
+                targets.push(&entry.target);
+            }
+        }
+
+        for target in targets {
+            let entries = entries.clone().filter(|entry| entry.level == *level && entry.target == target);
+
+            if !first_stream {
+                body.push(',');
+            }
+            first_stream = false;
+
+            body.push_str("{\"stream\":{\"level\":");
+            json::string(&mut body, level_name(*level));
+            body.push_str(",\"target\":");
+            json::string(&mut body, target);
+            for (key, value) in labels {
+                body.push(',');
+                json::string(&mut body, key);
+                body.push(':');
+                json::string(&mut body, value);
+            }
+            body.push_str("},\"values\":[");
+
+            let mut first_value = true;
+            let mut line = String::new();
+            for entry in entries {
+                if !first_value {
+                    body.push(',');
+                }
+                first_value = false;
+
+                line.clear();
+                let _ = write!(line, "[{}:{}] - {}", entry.file, entry.line, entry.message);
+                let _ = write!(body, "[\"{}\",", entry.timestamp);
+                json::string(&mut body, &line);
+                //Structured metadata only accepts string values
+                body.push_str(",{");
+                let mut number = String::new();
+                for_each_attribute(entry, |idx, key, value| {
+                    if idx > 0 {
+                        body.push(',');
+                    }
+                    json::string(&mut body, key);
+                    body.push(':');
+                    match value {
+                        Value::Str(value) => json::string(&mut body, value),
+                        Value::Int(value) => {
+                            number.clear();
+                            let _ = write!(number, "{}", value);
+                            json::string(&mut body, &number);
+                        },
+                    }
+                });
+                body.push_str("}]");
+            }
+            body.push_str("]}");
+        }
+    }
+
+    body.push_str("]}");
+    body
+}
+
+enum Value<'a> {
+    Str(&'a str),
+    Int(u32),
+}
+
+//Attributes of record, shared by Loki structured metadata and OTLP.
+fn for_each_attribute<F: FnMut(usize, &str, Value<'_>)>(entry: &Entry, mut fun: F) {
+    fun(0, "code.filepath", Value::Str(&entry.file));
+    fun(1, "code.lineno", Value::Int(entry.line));
+    fun(2, "code.namespace", Value::Str(&entry.module));
+    fun(3, "log.target", Value::Str(&entry.target));
+    for (idx, (key, value)) in entry.fields.iter().enumerate() {
+        fun(4 + idx, key, Value::Str(value));
+    }
+}
+
+fn otlp_attribute(body: &mut String, key: &str, value: &str) {
+    body.push_str("{\"key\":");
+    json::string(body, key);
+    body.push_str(",\"value\":{\"stringValue\":");
+    json::string(body, value);
+    body.push_str("}}");
+}
+
+fn otlp_body<'a>(labels: &[(String, String)], entries: impl Iterator<Item = &'a Entry>) -> String {
+    let mut body = String::from("{\"resourceLogs\":[{\"resource\":{\"attributes\":[");
+    for (idx, (key, value)) in labels.iter().enumerate() {
+        if idx > 0 {
+            body.push(',');
+        }
+        otlp_attribute(&mut body, key, value);
+    }
+    body.push_str("]},\"scopeLogs\":[{\"scope\":{\"name\":\"rogu\"},\"logRecords\":[");
+
+    for (idx, entry) in entries.enumerate() {
+        if idx > 0 {
+            body.push(',');
+        }
+
+        let _ = write!(body, "{{\"timeUnixNano\":\"{}\",\"severityNumber\":{},\"severityText\":", entry.timestamp, severity_number(entry.level));
+        json::string(&mut body, crate::data::level::name(entry.level));
+        body.push_str(",\"body\":{\"stringValue\":");
+        json::string(&mut body, &entry.message);
+        body.push_str("},\"attributes\":[");
+        for_each_attribute(entry, |idx, key, value| {
+            if idx > 0 {
+                body.push(',');
+            }
+            match value {
+                Value::Str(value) => otlp_attribute(&mut body, key, value),
+                Value::Int(value) => {
+                    body.push_str("{\"key\":");
+                    json::string(&mut body, key);
+                    let _ = write!(body, ",\"value\":{{\"intValue\":\"{}\"}}}}", value);
+                },
+            }
+        });
+        body.push_str("]}");
+    }
+
+    body.push_str("]}]}]}");
+    body
+}
+
+///Sink that pushes records to HTTP endpoint in batches.
+pub struct HttpSink {
+    sender: SyncSender<Message>,
+    dropped: Arc<AtomicUsize>,
+}
+
+impl HttpSink {
+    ///Creates new sink, spawning background thread to deliver records.
+    pub fn new(config: Config) -> io::Result<Self> {
+        let endpoint = Endpoint::parse(&config.url)?;
+        let (sender, receiver) = mpsc::sync_channel(config.queue_capacity);
+        let dropped = Arc::new(AtomicUsize::new(0));
+
+        let worker = Worker {
+            endpoint,
+            config,
+            dropped: dropped.clone(),
+            spill: VecDeque::new(),
+        };
+        thread::Builder::new().name("rogu-http".to_string()).spawn(move || worker.run(receiver))?;
+
+        Ok(Self {
+            sender,
+            dropped,
+        })
+    }
+
+    #[inline]
+    ///Returns number of records that were dropped due to full queue, spill buffer or rejected by server.
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl Sink for HttpSink {
+    fn write(&self, record: &Record<'_>) {
+        let entry = Entry::new(record);
+
+        match self.sender.try_send(Message::Record(entry)) {
+            Ok(()) => (),
+            Err(TrySendError::Full(_)) | Err(TrySendError::Disconnected(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    ///Waits until all queued records are attempted to be delivered.
+    fn flush(&self) {
+        let (ack, wait) = mpsc::channel();
+        if self.sender.send(Message::Flush(ack)).is_ok() {
+            let _ = wait.recv();
+        }
+    }
+}
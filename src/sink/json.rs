@@ -0,0 +1,39 @@
+//!Minimal JSON writing helpers
+
+use std::string::String;
+use core::fmt::Write;
+
+///Writes quoted and escaped JSON string.
+pub fn string(out: &mut String, text: &str) {
+    out.push('"');
+    for ch in text.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            ch if (ch as u32) < 0x20 => {
+                let _ = write!(out, "\\u{:04x}", ch as u32);
+            },
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(feature = "sqlite")]
+///Writes JSON object of string fields.
+pub fn object(out: &mut String, fields: &[(String, String)]) {
+    out.push('{');
+    for (idx, (key, value)) in fields.iter().enumerate() {
+        if idx > 0 {
+            out.push(',');
+        }
+        string(out, key);
+        out.push(':');
+        string(out, value);
+    }
+    out.push('}');
+}
+
@@ -0,0 +1,223 @@
+//!Additional record sinks.
+//!
+//!Sinks receive every record that passes level check, in addition to the platform logger.
+//!
+//!Sinks are registered once and live for the rest of program.
+//!Registry is lock-free for readers, so it can be used without `std`.
+
+#[cfg(feature = "std")]
+pub mod collapse;
+#[cfg(feature = "http")]
+pub mod http;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
+#[cfg(any(feature = "http", feature = "sqlite"))]
+mod json;
+
+use crate::Level;
+
+use core::fmt;
+use core::cell::UnsafeCell;
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+///Maximum number of sinks that can be registered.
+pub const MAX_SINKS: usize = 8;
+
+///Log record, passed to sinks.
+pub struct Record<'a> {
+    level: Level,
+    file: &'a str,
+    line: u32,
+    module: &'a str,
+    target: &'a str,
+    args: fmt::Arguments<'a>,
+}
+
+impl<'a> Record<'a> {
+    #[inline(always)]
+    ///Creates new record, with target set to `module`.
+    pub fn new(level: Level, file: &'a str, line: u32, module: &'a str, args: fmt::Arguments<'a>) -> Self {
+        Self {
+            level,
+            file,
+            line,
+            module,
+            target: module,
+            args,
+        }
+    }
+
+    #[inline(always)]
+    ///Sets record's target.
+    pub fn with_target(mut self, target: &'a str) -> Self {
+        self.target = target;
+        self
+    }
+
+    #[inline(always)]
+    ///Record's level.
+    pub fn level(&self) -> Level {
+        self.level
+    }
+
+    #[inline(always)]
+    ///Source file where record is created.
+    pub fn file(&self) -> &'a str {
+        self.file
+    }
+
+    #[inline(always)]
+    ///Line in the source file.
+    pub fn line(&self) -> u32 {
+        self.line
+    }
+
+    #[inline(always)]
+    ///Module path where record is created.
+    pub fn module(&self) -> &'a str {
+        self.module
+    }
+
+    #[inline(always)]
+    ///Logical channel of record, which defaults to module path.
+    pub fn target(&self) -> &'a str {
+        self.target
+    }
+
+    #[inline(always)]
+    ///Record's message.
+    pub fn args(&self) -> &fmt::Arguments<'a> {
+        &self.args
+    }
+}
+
+#[cfg(any(feature = "http", feature = "sqlite"))]
+//Owned copy of record, to be passed to background threads.
+pub(crate) struct Entry {
+    pub level: Level,
+    //Nanoseconds since UNIX epoch
+    pub timestamp: u128,
+    pub file: std::string::String,
+    pub line: u32,
+    pub module: std::string::String,
+    pub target: std::string::String,
+    pub message: std::string::String,
+    //Context fields
+    pub fields: std::vec::Vec<(std::string::String, std::string::String)>,
+}
+
+#[cfg(any(feature = "http", feature = "sqlite"))]
+impl Entry {
+    pub fn new(record: &Record<'_>) -> Self {
+        use std::string::ToString;
+
+        let timestamp = crate::clock::now().as_nanos();
+        let mut fields = std::vec::Vec::new();
+        crate::context::for_each(|key, value| fields.push((key.to_string(), value.to_string())));
+
+        Self {
+            level: record.level(),
+            timestamp,
+            file: record.file().to_string(),
+            line: record.line(),
+            module: record.module().to_string(),
+            target: record.target().to_string(),
+            message: record.args().to_string(),
+            fields,
+        }
+    }
+}
+
+///Log sink.
+///
+///Sink is called on the thread that logs, so implementations should avoid blocking.
+pub trait Sink: Sync {
+    ///Writes record.
+    fn write(&self, record: &Record<'_>);
+
+    #[inline(always)]
+    ///Flushes any buffered records.
+    fn flush(&self) {
+    }
+}
+
+struct Registry {
+    sinks: UnsafeCell<[Option<&'static dyn Sink>; MAX_SINKS]>,
+}
+
+//Slots are written only once under `LOCK` before `LEN` is published
+unsafe impl Sync for Registry {}
+
+static REGISTRY: Registry = Registry {
+    sinks: UnsafeCell::new([None; MAX_SINKS]),
+};
+static LEN: AtomicUsize = AtomicUsize::new(0);
+static LOCK: AtomicBool = AtomicBool::new(false);
+
+#[inline]
+fn sinks() -> &'static [Option<&'static dyn Sink>] {
+    let len = LEN.load(Ordering::Acquire);
+    //Slots beyond `len` may be written concurrently, so no reference to whole array is created
+    unsafe {
+        core::slice::from_raw_parts(REGISTRY.sinks.get() as *const Option<&'static dyn Sink>, len)
+    }
+}
+
+///Registers new sink.
+///
+///Returns `false` if there are already `MAX_SINKS` registered.
+pub fn add(sink: &'static dyn Sink) -> bool {
+    while LOCK.compare_exchange_weak(false, true, Ordering::Acquire, Ordering::Relaxed).is_err() {
+        core::hint::spin_loop();
+    }
+
+    let len = LEN.load(Ordering::Relaxed);
+    let result = if len < MAX_SINKS {
+        unsafe {
+            (REGISTRY.sinks.get() as *mut Option<&'static dyn Sink>).add(len).write(Some(sink));
+        }
+        LEN.store(len + 1, Ordering::Release);
+        true
+    } else {
+        false
+    };
+
+    LOCK.store(false, Ordering::Release);
+    result
+}
+
+#[cfg(feature = "std")]
+///Registers new sink, leaking it for the rest of program.
+///
+///Returns `false` if there are already `MAX_SINKS` registered.
+pub fn add_boxed(sink: std::boxed::Box<dyn Sink + Send>) -> bool {
+    add(std::boxed::Box::leak(sink))
+}
+
+#[inline]
+///Returns whether any sink is registered.
+pub fn is_active() -> bool {
+    LEN.load(Ordering::Relaxed) != 0
+}
+
+///Flushes all registered sinks.
+pub fn flush() {
+    for sink in sinks().iter().flatten() {
+        sink.flush();
+    }
+}
+
+///Writes record to all registered sinks.
+pub fn write(record: &Record<'_>) {
+    for sink in sinks().iter().flatten() {
+        sink.write(record);
+    }
+}
+
+#[doc(hidden)]
+#[inline]
+pub fn dispatch(level: Level, file: &str, line: u32, module: &str, target: &str, args: fmt::Arguments<'_>) {
+    if is_active() {
+        write(&Record::new(level, file, line, module, args).with_target(target));
+    }
+}
@@ -0,0 +1,265 @@
+//!SQLite sink.
+//!
+//!Records are written by background thread in batched transactions into table:
+//!
+//!```sql
+//!CREATE TABLE logs (
+//!    id INTEGER PRIMARY KEY AUTOINCREMENT,
+//!    ts INTEGER NOT NULL, -- milliseconds since UNIX epoch
+//!    level TEXT NOT NULL,
+//!    module TEXT NOT NULL,
+//!    target TEXT NOT NULL,
+//!    file TEXT NOT NULL,
+//!    line INTEGER NOT NULL,
+//!    message TEXT NOT NULL,
+//!    fields TEXT NOT NULL -- JSON object of context fields
+//!)
+//!```
+//!
+//!After each transaction oldest rows above configured limit are removed.
+//!
+//!Example query:
+//!
+//!```sql
+//!SELECT datetime(ts / 1000, 'unixepoch'), level, message FROM logs WHERE level = 'ERROR' ORDER BY id DESC LIMIT 10
+//!```
+
+use super::{json, Entry, Record, Sink};
+
+use std::io;
+use std::path::PathBuf;
+use std::string::{String, ToString};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, SyncSender, Receiver, RecvTimeoutError, TrySendError};
+use std::time::{Duration, Instant};
+use std::vec::Vec;
+use std::thread;
+
+pub use rusqlite::Error;
+
+///SQLite sink configuration.
+pub struct Config {
+    path: PathBuf,
+    table: String,
+    max_batch_size: usize,
+    max_batch_age: Duration,
+    queue_capacity: usize,
+    max_rows: u64,
+}
+
+impl Config {
+    ///Creates new config to write into database at `path`.
+    pub fn new<P: Into<PathBuf>>(path: P) -> Self {
+        Self {
+            path: path.into(),
+            table: "logs".to_string(),
+            max_batch_size: 100,
+            max_batch_age: Duration::from_secs(1),
+            queue_capacity: 1024,
+            max_rows: 100_000,
+        }
+    }
+
+    ///Sets table name.
+    ///
+    ///Defaults to `logs`.
+    pub fn table(mut self, table: &str) -> Self {
+        self.table = table.to_string();
+        self
+    }
+
+    ///Sets maximum number of records written in single transaction.
+    ///
+    ///Defaults to 100.
+    pub fn max_batch_size(mut self, size: usize) -> Self {
+        self.max_batch_size = core::cmp::max(size, 1);
+        self
+    }
+
+    ///Sets maximum time record waits in batch before being written.
+    ///
+    ///Defaults to 1 second.
+    pub fn max_batch_age(mut self, age: Duration) -> Self {
+        self.max_batch_age = age;
+        self
+    }
+
+    ///Sets number of records that can be queued for background thread.
+    ///
+    ///Defaults to 1024.
+    pub fn queue_capacity(mut self, capacity: usize) -> Self {
+        self.queue_capacity = core::cmp::max(capacity, 1);
+        self
+    }
+
+    ///Sets maximum number of rows to retain, `0` disables retention.
+    ///
+    ///Defaults to 100000.
+    pub fn max_rows(mut self, rows: u64) -> Self {
+        self.max_rows = rows;
+        self
+    }
+}
+
+enum Message {
+    Record(Entry),
+    Flush(mpsc::Sender<()>),
+}
+
+struct Worker {
+    connection: rusqlite::Connection,
+    insert: String,
+    retain: String,
+    config: Config,
+    dropped: Arc<AtomicUsize>,
+}
+
+impl Worker {
+    fn run(self, receiver: Receiver<Message>) {
+        let mut batch = Vec::new();
+        let mut batch_start = Instant::now();
+
+        loop {
+            let timeout = match batch.is_empty() {
+                true => Duration::from_secs(3600),
+                false => self.config.max_batch_age.saturating_sub(batch_start.elapsed()),
+            };
+
+            match receiver.recv_timeout(timeout) {
+                Ok(Message::Record(entry)) => {
+                    if batch.is_empty() {
+                        batch_start = Instant::now();
+                    }
+                    batch.push(entry);
+                    if batch.len() >= self.config.max_batch_size {
+                        self.commit(&mut batch);
+                    }
+                },
+                Ok(Message::Flush(ack)) => {
+                    let mut acks = std::vec![ack];
+                    while let Ok(message) = receiver.try_recv() {
+                        match message {
+                            Message::Record(entry) => batch.push(entry),
+                            Message::Flush(ack) => acks.push(ack),
+                        }
+                    }
+                    self.commit(&mut batch);
+                    for ack in acks {
+                        let _ = ack.send(());
+                    }
+                },
+                Err(RecvTimeoutError::Timeout) => self.commit(&mut batch),
+                Err(RecvTimeoutError::Disconnected) => {
+                    self.commit(&mut batch);
+                    break;
+                },
+            }
+        }
+    }
+
+    fn commit(&self, batch: &mut Vec<Entry>) {
+        if batch.is_empty() {
+            return;
+        }
+
+        if self.write(batch).is_err() {
+            self.dropped.fetch_add(batch.len(), Ordering::Relaxed);
+        }
+        batch.clear();
+    }
+
+    fn write(&self, batch: &[Entry]) -> Result<(), Error> {
+        let transaction = self.connection.unchecked_transaction()?;
+        {
+            let mut insert = transaction.prepare_cached(&self.insert)?;
+            let mut fields = String::new();
+            for entry in batch {
+                let ts = (entry.timestamp / 1_000_000) as i64;
+                fields.clear();
+                json::object(&mut fields, &entry.fields);
+                insert.execute(rusqlite::params![ts, crate::data::level::name(entry.level), entry.module, entry.target, entry.file, entry.line, entry.message, fields])?;
+            }
+        }
+
+        if self.config.max_rows > 0 {
+            transaction.execute(&self.retain, [self.config.max_rows as i64])?;
+        }
+
+        transaction.commit()
+    }
+}
+
+fn io_error(error: Error) -> io::Error {
+    io::Error::other(error)
+}
+
+///Sink that writes records into SQLite database.
+pub struct SqliteSink {
+    sender: SyncSender<Message>,
+    dropped: Arc<AtomicUsize>,
+}
+
+impl SqliteSink {
+    ///Opens database, creating table if necessary, and spawns background thread to write records.
+    ///
+    ///Database errors are returned as `io::Error`, wrapping [Error](enum.Error.html).
+    pub fn new(config: Config) -> io::Result<Self> {
+        let connection = rusqlite::Connection::open(&config.path).map_err(io_error)?;
+        let table = std::format!("\"{}\"", config.table.replace('"', "\"\""));
+
+        connection.execute_batch(&std::format!("CREATE TABLE IF NOT EXISTS {} (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            ts INTEGER NOT NULL,
+            level TEXT NOT NULL,
+            module TEXT NOT NULL,
+            target TEXT NOT NULL,
+            file TEXT NOT NULL,
+            line INTEGER NOT NULL,
+            message TEXT NOT NULL,
+            fields TEXT NOT NULL
+        )", table)).map_err(io_error)?;
+
+        let (sender, receiver) = mpsc::sync_channel(config.queue_capacity);
+        let dropped = Arc::new(AtomicUsize::new(0));
+
+        let worker = Worker {
+            connection,
+            insert: std::format!("INSERT INTO {} (ts, level, module, target, file, line, message, fields) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)", table),
+            retain: std::format!("DELETE FROM {0} WHERE id <= (SELECT MAX(id) FROM {0}) - ?1", table),
+            config,
+            dropped: dropped.clone(),
+        };
+        thread::Builder::new().name("rogu-sqlite".to_string()).spawn(move || worker.run(receiver))?;
+
+        Ok(Self {
+            sender,
+            dropped,
+        })
+    }
+
+    #[inline]
+    ///Returns number of records that were dropped due to full queue or failed transaction.
+    pub fn dropped(&self) -> usize {
+        self.dropped.load(Ordering::Relaxed)
+    }
+}
+
+impl Sink for SqliteSink {
+    fn write(&self, record: &Record<'_>) {
+        match self.sender.try_send(Message::Record(Entry::new(record))) {
+            Ok(()) => (),
+            Err(TrySendError::Full(_)) | Err(TrySendError::Disconnected(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+
+    ///Waits until all queued records are written.
+    fn flush(&self) {
+        let (ack, wait) = mpsc::channel();
+        if self.sender.send(Message::Flush(ack)).is_ok() {
+            let _ = wait.recv();
+        }
+    }
+}
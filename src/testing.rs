@@ -0,0 +1,182 @@
+//!Capture of records for tests.
+//!
+//!Records are captured through [sink](../sink/index.html), so they must pass level check and are captured only
+//!on the thread, that started capture.
+//!
+//!```rust
+//!use rogu::Level;
+//!
+//!rogu::set_level(Level::INFO);
+//!
+//!let records = rogu::testing::capture(|| {
+//!    rogu::warn!("connection timeout after {}s", 5);
+//!    rogu::assert_logged!(Level::WARN, contains "timeout");
+//!});
+//!
+//!assert_eq!(records.len(), 1);
+//!rogu::assert_logged!(records, Level::WARN, "connection timeout after 5s");
+//!```
+
+use crate::Level;
+use crate::sink::{self, Record, Sink};
+
+use core::cell::RefCell;
+use core::fmt;
+use std::string::{String, ToString};
+use std::sync::{Mutex, PoisonError};
+use std::vec::Vec;
+
+//Set only once sink is registered, so that every capture fails while it is not.
+static IS_INIT: Mutex<bool> = Mutex::new(false);
+
+std::thread_local! {
+    static CAPTURES: RefCell<Vec<Vec<Captured>>> = const { RefCell::new(Vec::new()) };
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+///Captured record.
+pub struct Captured {
+    ///Record's level.
+    pub level: Level,
+    ///Source file where record is created.
+    pub file: String,
+    ///Line in the source file.
+    pub line: u32,
+    ///Module path where record is created.
+    pub module: String,
+    ///Logical channel of record.
+    pub target: String,
+    ///Record's message.
+    pub message: String,
+    ///Context fields, active when record is written.
+    pub fields: Vec<(String, String)>,
+}
+
+impl fmt::Display for Captured {
+    fn fmt(&self, fmt: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(fmt, "{:<5} - [{}:{}] - {}", crate::data::level::name(self.level), self.file, self.line, self.message)
+    }
+}
+
+struct CaptureSink;
+
+impl Sink for CaptureSink {
+    fn write(&self, record: &Record<'_>) {
+        let _ = CAPTURES.try_with(|captures| {
+            if let Some(records) = captures.borrow_mut().last_mut() {
+                records.push(Captured {
+                    level: record.level(),
+                    file: record.file().to_string(),
+                    line: record.line(),
+                    module: record.module().to_string(),
+                    target: record.target().to_string(),
+                    message: record.args().to_string(),
+                    fields: {
+                        let mut fields = Vec::new();
+                        crate::context::for_each(|key, value| fields.push((key.to_string(), value.to_string())));
+                        fields
+                    },
+                });
+            }
+        });
+    }
+}
+
+static CAPTURE_SINK: CaptureSink = CaptureSink;
+
+///Capture guard, collecting records of current thread until dropped.
+///
+///Captures can be nested, in which case records go into innermost capture.
+pub struct Capture {
+    //Captures are per thread
+    _not_send: core::marker::PhantomData<*const ()>,
+}
+
+impl Capture {
+    ///Starts capturing records on current thread.
+    ///
+    ///Panics, if capture sink cannot be registered, because there are already `MAX_SINKS` sinks.
+    pub fn start() -> Self {
+        let is_init = {
+            let mut is_init = IS_INIT.lock().unwrap_or_else(PoisonError::into_inner);
+            if !*is_init {
+                *is_init = sink::add(&CAPTURE_SINK);
+            }
+            *is_init
+        };
+        assert!(is_init, "Unable to register capture sink: too many sinks");
+
+        CAPTURES.with(|captures| captures.borrow_mut().push(Vec::new()));
+        Self {
+            _not_send: core::marker::PhantomData,
+        }
+    }
+
+    ///Returns copy of records captured so far.
+    pub fn records(&self) -> Vec<Captured> {
+        records()
+    }
+
+    ///Stops capture, returning captured records.
+    pub fn finish(self) -> Vec<Captured> {
+        let records = CAPTURES.with(|captures| captures.borrow_mut().pop().unwrap_or_default());
+        core::mem::forget(self);
+        records
+    }
+}
+
+impl Drop for Capture {
+    #[inline]
+    fn drop(&mut self) {
+        let _ = CAPTURES.try_with(|captures| captures.borrow_mut().pop());
+    }
+}
+
+///Runs `fun`, returning records it emitted on current thread.
+pub fn capture<F: FnOnce()>(fun: F) -> Vec<Captured> {
+    let capture = Capture::start();
+    fun();
+    capture.finish()
+}
+
+///Returns copy of records captured so far by innermost capture on current thread.
+pub fn records() -> Vec<Captured> {
+    CAPTURES.with(|captures| captures.borrow().last().cloned().unwrap_or_default())
+}
+
+#[doc(hidden)]
+pub fn assert_logged<P: Fn(&str) -> bool>(records: &[Captured], level: Level, expected: fmt::Arguments<'_>, predicate: P) {
+    if !records.iter().any(|record| record.level == level && predicate(&record.message)) {
+        let mut captured = String::new();
+        for record in records {
+            captured.push_str("\n  ");
+            captured.push_str(&record.to_string());
+        }
+        panic!("No {} record {} among captured:{}", crate::data::level::name(level), expected, captured);
+    }
+}
+
+#[macro_export]
+///Asserts that record with specified level and message is captured.
+///
+///Checks either records of active capture on current thread or provided list of [records](testing/struct.Captured.html).
+///
+///Message is specified either as exact text or as `contains <text>`.
+macro_rules! assert_logged {
+    ($level:expr, contains $text:expr $(,)?) => {
+        $crate::assert_logged!(&$crate::testing::records(), $level, contains $text)
+    };
+    ($level:expr, $text:expr $(,)?) => {
+        $crate::assert_logged!(&$crate::testing::records(), $level, $text)
+    };
+    ($records:expr, $level:expr, contains $text:expr $(,)?) => {
+        match &$text {
+            text => $crate::testing::assert_logged(&$records, $level, core::format_args!("containing {:?}", text), |message| message.contains(&text[..])),
+        }
+    };
+    ($records:expr, $level:expr, $text:expr $(,)?) => {
+        match &$text {
+            text => $crate::testing::assert_logged(&$records, $level, core::format_args!("{:?}", text), |message| message == &text[..]),
+        }
+    };
+}
@@ -0,0 +1,148 @@
+//!`tracing` integration.
+//!
+//![Layer](struct.Layer.html) writes `tracing` events through platform logger, using the same
+//!format as rogu macros with spans' context prepended to the message:
+//!
+//!```text
+//!INFO  - [src/main.rs:10] - request{id=1}:handle: message key=value
+//!```
+//!
+//!Events are filtered by [is_target_enabled](../fn.is_target_enabled.html), taking into account level of their target.
+
+use std::string::String;
+use core::fmt::{self, Write};
+
+use tracing_core::{field, span, Event, Metadata, Subscriber, Interest};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+
+impl From<&tracing_core::Level> for crate::Level {
+    #[inline(always)]
+    fn from(level: &tracing_core::Level) -> Self {
+        match *level {
+            tracing_core::Level::ERROR => crate::Level::ERROR,
+            tracing_core::Level::WARN => crate::Level::WARN,
+            tracing_core::Level::INFO => crate::Level::INFO,
+            tracing_core::Level::DEBUG => crate::Level::DEBUG,
+            _ => crate::Level::TRACE,
+        }
+    }
+}
+
+//Span's fields formatted as `key=value`.
+struct SpanFields(Visitor);
+
+#[derive(Default)]
+struct Visitor {
+    message: String,
+    fields: String,
+}
+
+impl field::Visit for Visitor {
+    fn record_str(&mut self, field: &field::Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &field::Field, value: &dyn fmt::Debug) {
+        let _ = match field.name() {
+            "message" => write!(self.message, "{:?}", value),
+            name => match self.fields.is_empty() {
+                true => write!(self.fields, "{}={:?}", name, value),
+                false => write!(self.fields, " {}={:?}", name, value),
+            }
+        };
+    }
+}
+
+///`tracing_subscriber` layer that writes events through platform logger.
+pub struct Layer;
+
+impl<S: Subscriber + for<'a> LookupSpan<'a>> tracing_subscriber::Layer<S> for Layer {
+    #[inline]
+    fn register_callsite(&self, _: &'static Metadata<'static>) -> Interest {
+        //Level can be changed at runtime, so interest must not be cached.
+        Interest::sometimes()
+    }
+
+    #[inline]
+    fn enabled(&self, meta: &Metadata<'_>, _: Context<'_, S>) -> bool {
+        meta.is_span() || crate::is_target_enabled(meta.target(), meta.level().into())
+    }
+
+    fn on_new_span(&self, attrs: &span::Attributes<'_>, id: &span::Id, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut visitor = Visitor::default();
+            attrs.record(&mut visitor);
+            span.extensions_mut().insert(SpanFields(visitor));
+        }
+    }
+
+    fn on_record(&self, id: &span::Id, values: &span::Record<'_>, ctx: Context<'_, S>) {
+        if let Some(span) = ctx.span(id) {
+            let mut extensions = span.extensions_mut();
+            if let Some(fields) = extensions.get_mut::<SpanFields>() {
+                values.record(&mut fields.0);
+            }
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let meta = event.metadata();
+        let level = meta.level().into();
+
+        if !crate::is_target_enabled(meta.target(), level) {
+            return;
+        }
+
+        let mut message = String::new();
+        if let Some(scope) = ctx.event_scope(event) {
+            let mut has_spans = false;
+            for span in scope.from_root() {
+                message.push_str(span.name());
+                if let Some(fields) = span.extensions().get::<SpanFields>() {
+                    if !fields.0.fields.is_empty() {
+                        let _ = write!(message, "{{{}}}", fields.0.fields);
+                    }
+                }
+                message.push(':');
+                has_spans = true;
+            }
+
+            if has_spans {
+                message.push(' ');
+            }
+        }
+        let mut visitor = Visitor::default();
+        event.record(&mut visitor);
+        message.push_str(&visitor.message);
+        if !visitor.fields.is_empty() {
+            if !visitor.message.is_empty() {
+                message.push(' ');
+            }
+            message.push_str(&visitor.fields);
+        }
+
+        let mut line = String::new();
+        let _ = match (meta.file(), meta.line()) {
+            (Some(file), Some(num)) => writeln!(line, "- [{}:{}] - {}", crate::out::file_name(file), num, message),
+            _ => writeln!(line, "{}", message),
+        };
+
+        #[cfg(not(feature = "ufmt"))]
+        {
+            if crate::out::is_direct() {
+                let _ = crate::out::by_level(level, "").write_str(&line);
+            }
+            crate::sink::dispatch(level, meta.file().unwrap_or(""), meta.line().unwrap_or(0), meta.module_path().unwrap_or(""), meta.target(), format_args!("{}", message));
+        }
+
+        #[cfg(feature = "ufmt")]
+        {
+            let _ = ufmt::uWrite::write_str(&mut crate::out::by_level(level, ""), &line);
+        }
+    }
+}
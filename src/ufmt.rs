@@ -1,5 +1,61 @@
 pub use ::ufmt::*;
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __log_write {
+    ($level:ident, $out:ident, ) => {
+        $crate::__log_write!($level, $out, "")
+    };
+    ($level:ident, $out:ident, target: $target:expr, $($arg:tt)*) => {
+        match $target {
+            target => if $crate::is_target_enabled(target, $crate::Level::$level) {
+                //ufmt macros refer to `ufmt` by bare path, so make it available to the caller's scope.
+                use $crate::ufmt;
+                let mut out = $crate::Out::$out(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") );
+                let _ = ufmt::uWrite::write_str(&mut out, target);
+                let _ = ufmt::uWrite::write_str(&mut out, ": ");
+                let _ = ufmt::uwriteln!(out, $($arg)*);
+            }
+        }
+    };
+    ($level:ident, $out:ident, $($arg:tt)*) => {
+        if $crate::is_enabled($crate::Level::$level) {
+            //ufmt macros refer to `ufmt` by bare path, so make it available to the caller's scope.
+            use $crate::ufmt;
+            let _ = ufmt::uwriteln!($crate::Out::$out(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") ), $($arg)*);
+        }
+    }
+}
+
+#[macro_export]
+///Writes fatal log, flushes sinks and terminates program.
+///
+///Fatal log is written regardless of level and compile time features.
+///Program is terminated by handler set with [set_fatal_handler](fn.set_fatal_handler.html), or by abort.
+macro_rules! fatal {
+    ($($arg:tt)*) => {{
+        $crate::__log_write!(FATAL, fatal, $($arg)*);
+        $crate::__fatal_terminate()
+    }}
+}
+
+#[cfg(all(feature = "extended_levels", any(all(debug_assertions, feature = "level_critical_off"), all(not(debug_assertions), feature = "release_level_critical_off") )))]
+#[macro_export]
+///Writes critical log
+macro_rules! critical {
+    ($($arg:tt)*) => {
+    }
+}
+
+#[cfg(all(feature = "extended_levels", any(all(debug_assertions, not(feature = "level_critical_off")), all(not(debug_assertions), not(feature = "release_level_critical_off")) )))]
+#[macro_export]
+///Writes critical log
+macro_rules! critical {
+    ($($arg:tt)*) => {
+        $crate::__log_write!(CRITICAL, critical, $($arg)*)
+    }
+}
+
 #[cfg(any(all(debug_assertions, feature = "level_error_off"), all(not(debug_assertions), feature = "release_level_error_off") ) )]
 #[macro_export]
 ///Writes error log
@@ -13,9 +69,7 @@ macro_rules! error {
 ///Writes error log
 macro_rules! error {
     ($($arg:tt)*) => {
-        if $crate::is_enabled($crate::Level::ERROR) {
-            let _ = ufmt::uwriteln!($crate::Out::error(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") ), $($arg)*);
-        }
+        $crate::__log_write!(ERROR, error, $($arg)*)
     }
 }
 
@@ -32,9 +86,24 @@ macro_rules! warn {
 ///Writes warn log
 macro_rules! warn {
     ($($arg:tt)*) => {
-        if $crate::is_enabled($crate::Level::WARN) {
-            let _ = ufmt::uwriteln!($crate::Out::warn(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") ), $($arg)*);
-        }
+        $crate::__log_write!(WARN, warn, $($arg)*)
+    }
+}
+
+#[cfg(all(feature = "extended_levels", any(all(debug_assertions, feature = "level_notice_off"), all(not(debug_assertions), feature = "release_level_notice_off") )))]
+#[macro_export]
+///Writes notice log
+macro_rules! notice {
+    ($($arg:tt)*) => {
+    }
+}
+
+#[cfg(all(feature = "extended_levels", any(all(debug_assertions, not(feature = "level_notice_off")), all(not(debug_assertions), not(feature = "release_level_notice_off")) )))]
+#[macro_export]
+///Writes notice log
+macro_rules! notice {
+    ($($arg:tt)*) => {
+        $crate::__log_write!(NOTICE, notice, $($arg)*)
     }
 }
 
@@ -51,9 +120,7 @@ macro_rules! info {
 ///Writes info log
 macro_rules! info {
     ($($arg:tt)*) => {
-        if $crate::is_enabled($crate::Level::INFO) {
-            let _ = ufmt::uwriteln!($crate::Out::info(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") ), $($arg)*);
-        }
+        $crate::__log_write!(INFO, info, $($arg)*)
     }
 }
 
@@ -70,9 +137,7 @@ macro_rules! debug {
 ///Writes debug log
 macro_rules! debug {
     ($($arg:tt)*) => {
-        if $crate::is_enabled($crate::Level::DEBUG) {
-            let _ = ufmt::uwriteln!($crate::Out::debug(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") ), $($arg)*);
-        }
+        $crate::__log_write!(DEBUG, debug, $($arg)*)
     }
 }
 
@@ -89,8 +154,6 @@ macro_rules! trace {
 ///Writes trace log
 macro_rules! trace {
     ($($arg:tt)*) => {
-        if $crate::is_enabled($crate::Level::TRACE) {
-            let _ = ufmt::uwriteln!($crate::Out::trace(core::concat!("- [", core::file!(), ":", core::line!(), "] - ") ), $($arg)*);
-        }
+        $crate::__log_write!(TRACE, trace, $($arg)*)
     }
 }
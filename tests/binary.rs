@@ -0,0 +1,90 @@
+#![cfg(all(feature = "binary", feature = "decoder", target_os = "linux"))]
+
+use rogu::binary::decode::{elf_section, Decoder, Error};
+
+use std::sync::Mutex;
+
+static WRITES: Mutex<Vec<Vec<u8>>> = Mutex::new(Vec::new());
+
+fn hook(bytes: &[u8]) {
+    WRITES.lock().unwrap().push(bytes.to_vec());
+}
+
+#[test]
+fn should_encode_records_for_host_decoding() {
+    rogu::set_level(rogu::Level::INFO);
+    rogu::set_write_hook(hook);
+
+    let line = line!() + 1;
+    rogu::info!("hello {} {:?} {:x} {} {}", "binary", "str", 255u32, -5i16, true);
+    rogu::debug!("filtered {}", 1u8);
+    let long = "x".repeat(1000);
+    rogu::warn!("{} {} {{escaped}}", long.as_str(), 'c');
+    rogu::error!(target: "audit", "code {}", 7u8);
+
+    let elf = std::fs::read("/proc/self/exe").unwrap();
+    let decoder = Decoder::from_elf(&elf).expect("to have interned strings");
+
+    let writes = WRITES.lock().unwrap();
+    assert_eq!(writes.len(), 3);
+    assert!(writes[0].len() < 40);
+    assert_eq!(writes[1].len(), rogu::binary::FRAME_CAPACITY);
+
+    let (text, len) = decoder.decode(&writes[0]).unwrap();
+    assert_eq!(len, writes[0].len());
+    assert_eq!(text, format!("INFO  - [tests/binary.rs:{}] - hello binary \"str\" ff -5 true", line));
+
+    //Long string is truncated, while char no longer fits
+    let (text, _) = decoder.decode(&writes[1]).unwrap();
+    let expected = format!("WARN  - [tests/binary.rs:{}] - ", line + 3);
+    assert!(text.starts_with(&expected));
+    assert!(text.ends_with("x … {escaped}"));
+
+    let (text, _) = decoder.decode(&writes[2]).unwrap();
+    assert_eq!(text, format!("ERROR - [tests/binary.rs:{}] - audit: code 7", line + 4));
+
+    let stream = writes[..2].concat();
+    let (records, consumed) = decoder.decode_all(&stream[..stream.len() - 1]);
+    assert_eq!(records.len(), 1);
+    assert_eq!(consumed, writes[0].len());
+    assert_eq!(decoder.decode(&stream[consumed..stream.len() - 1]), Err(Error::Incomplete));
+}
+
+#[test]
+fn should_pad_level_as_text_loggers() {
+    let strings = b"CRITICAL\x1fsrc/main.rs\x1f1\x1fdisk failed\0NOTICE\x1fsrc/main.rs\x1f2\x1ffailover\0WARN\x1fsrc/main.rs\x1f3\x1fretry\0";
+    let decoder = Decoder::new(strings);
+
+    let notice = strings.iter().position(|byte| *byte == 0).unwrap() as u8 + 1;
+    let warn = notice + strings[notice as usize..].iter().position(|byte| *byte == 0).unwrap() as u8 + 1;
+    assert_eq!(decoder.decode(&[1, 0, 0]).unwrap().0, "CRITICAL - [src/main.rs:1] - disk failed");
+    assert_eq!(decoder.decode(&[1, 0, notice]).unwrap().0, "NOTICE - [src/main.rs:2] - failover");
+    assert_eq!(decoder.decode(&[1, 0, warn]).unwrap().0, "WARN  - [src/main.rs:3] - retry");
+}
+
+#[test]
+#[cfg(all(target_pointer_width = "64", target_endian = "little"))]
+fn should_reject_malformed_elf() {
+    let elf = std::fs::read("/proc/self/exe").unwrap();
+    assert!(elf_section(&elf, rogu::binary::SECTION).is_some());
+    assert!(elf_section(&elf[..64], rogu::binary::SECTION).is_none());
+
+    //Section headers offset and entry size overflow
+    let mut header = elf[..64].to_vec();
+    header[0x28..0x30].copy_from_slice(&u64::MAX.to_le_bytes());
+    header[0x3a..0x3c].copy_from_slice(&u16::MAX.to_le_bytes());
+    header[0x3e..0x40].copy_from_slice(&u16::MAX.to_le_bytes());
+    assert!(elf_section(&header, rogu::binary::SECTION).is_none());
+
+    //Section header with content out of file
+    let mut elf = header;
+    elf[0x28..0x30].copy_from_slice(&64u64.to_le_bytes());
+    elf[0x3a..0x3c].copy_from_slice(&64u16.to_le_bytes());
+    elf[0x3c..0x3e].copy_from_slice(&1u16.to_le_bytes());
+    elf[0x3e..0x40].copy_from_slice(&0u16.to_le_bytes());
+    let mut section = [0u8; 64];
+    section[0x18..0x20].copy_from_slice(&u64::MAX.to_le_bytes());
+    section[0x20..0x28].copy_from_slice(&2u64.to_le_bytes());
+    elf.extend_from_slice(&section);
+    assert!(elf_section(&elf, rogu::binary::SECTION).is_none());
+}
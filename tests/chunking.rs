@@ -0,0 +1,55 @@
+use proptest::prelude::*;
+
+use rogu::{__Buffer as Buffer, __CONTINUATION as CONTINUATION};
+
+const PREFIX: &str = "ERROR - [tests/chunking.rs:1] - ";
+
+//Writes record as `writeln!` does: prefix, message and newline as separate pieces.
+fn write<const N: usize>(message: &str) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut buffer = Buffer::<N>::new();
+    for text in [PREFIX, message, "\n"] {
+        buffer.write_text(text, |bytes| chunks.push(String::from_utf8(bytes.to_vec()).expect("valid UTF-8")));
+    }
+    chunks
+}
+
+fn check<const N: usize>(message: &str) -> Result<(), TestCaseError> {
+    let chunks = write::<N>(message);
+    prop_assert!(!chunks.is_empty());
+
+    for (idx, chunk) in chunks.iter().enumerate() {
+        prop_assert!(chunk.len() <= N);
+        prop_assert!(chunk.ends_with('\n'));
+        prop_assert_eq!(chunk.matches('\n').count(), 1);
+        prop_assert_eq!(idx > 0, chunk.starts_with(CONTINUATION));
+    }
+
+    let text = chunks.iter().enumerate().map(|(idx, chunk)| match idx {
+        0 => &chunk[..chunk.len() - 1],
+        _ => &chunk[CONTINUATION.len()..chunk.len() - 1],
+    }).collect::<String>();
+    prop_assert_eq!(text, format!("{}{}", PREFIX, message));
+    Ok(())
+}
+
+proptest! {
+    #[test]
+    fn should_split_records_on_char_boundaries(message in "\\PC{0,2000}", words in proptest::collection::vec("\\PC{1,12}", 0..300)) {
+        for message in [message, words.join(" ")] {
+            check::<64>(&message)?;
+            check::<256>(&message)?;
+            check::<4096>(&message)?;
+        }
+    }
+}
+
+#[test]
+fn should_prefer_splitting_on_whitespace() {
+    let message = "word ".repeat(200);
+    let chunks = write::<512>(&message);
+    assert!(chunks.len() > 1);
+    for chunk in &chunks[..chunks.len() - 1] {
+        assert!(chunk.ends_with("word \n"), "{:?}", chunk);
+    }
+}
@@ -0,0 +1,91 @@
+#![cfg(all(feature = "std", not(feature = "ufmt")))]
+
+mod common;
+
+use common::Capture;
+use rogu::sink::{Record, Sink};
+use rogu::sink::collapse::Collapse;
+use rogu::Level;
+
+use core::time::Duration;
+use std::sync::Mutex;
+
+static CLOCK: rogu::clock::Mock = rogu::clock::Mock::fixed(Duration::from_secs(1_600_000_000));
+//Tests are run concurrently, while clock is global.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn write(sink: &impl Sink, level: Level, line: u32, message: &str) {
+    sink.write(&Record::new(level, "src/main.rs", line, "main", format_args!("{}", message)));
+}
+
+#[test]
+fn should_collapse_duplicate_records() {
+    let _serial = SERIAL.lock().unwrap_or_else(|error| error.into_inner());
+    rogu::clock::set(&CLOCK);
+    let sink = Collapse::new(Capture::new(|record| format!("{:?} {}:{} {}", record.level(), record.file(), record.line(), record.args())), Duration::from_secs(30));
+
+    write(&sink, Level::ERROR, 1, "connection refused");
+    write(&sink, Level::ERROR, 1, "connection refused");
+    write(&sink, Level::ERROR, 1, "connection refused");
+    //Different callsite
+    write(&sink, Level::ERROR, 2, "connection refused");
+    write(&sink, Level::WARN, 3, "retry");
+    write(&sink, Level::WARN, 3, "retry");
+    CLOCK.advance(Duration::from_secs(30));
+    write(&sink, Level::WARN, 3, "retry");
+    write(&sink, Level::WARN, 3, "retry");
+    sink.flush();
+    sink.flush();
+    write(&sink, Level::WARN, 3, "retry");
+    write(&sink, Level::WARN, 3, "retry again");
+
+    assert_eq!(*sink.inner().records(), [
+        "ERROR src/main.rs:1 connection refused",
+        "ERROR src/main.rs:1 last message repeated 2 times",
+        "ERROR src/main.rs:2 connection refused",
+        "WARN src/main.rs:3 retry",
+        "WARN src/main.rs:3 last message repeated 2 times",
+        "WARN src/main.rs:3 last message repeated 1 times",
+        "WARN src/main.rs:3 last message repeated 1 times",
+        "WARN src/main.rs:3 retry again",
+    ]);
+}
+
+//Inner sink, which logs through the same adapter, while its lock is held.
+struct Reentrant(Mutex<Vec<String>>);
+
+impl Sink for Reentrant {
+    fn write(&self, record: &Record<'_>) {
+        let message = record.args().to_string();
+        self.0.lock().unwrap().push(message.clone());
+        if message == "trigger" {
+            write(&REENTRANT, Level::INFO, 10, "nested");
+        }
+    }
+}
+
+static REENTRANT: Collapse<Reentrant> = Collapse::new(Reentrant(Mutex::new(Vec::new())), Duration::from_secs(3600));
+
+#[test]
+fn should_pass_through_records_of_wrapped_sink() {
+    let _serial = SERIAL.lock().unwrap_or_else(|error| error.into_inner());
+    rogu::clock::set(&CLOCK);
+
+    write(&REENTRANT, Level::INFO, 1, "trigger");
+    for _ in 0..3 {
+        write(&REENTRANT, Level::INFO, 2, "repeat");
+    }
+    REENTRANT.tick();
+    assert_eq!(REENTRANT.inner().0.lock().unwrap().len(), 3);
+
+    //Summary is written after timeout even if nothing else arrives
+    CLOCK.advance(Duration::from_secs(3600));
+    REENTRANT.tick();
+    REENTRANT.tick();
+    assert_eq!(*REENTRANT.inner().0.lock().unwrap(), [
+        "trigger",
+        "nested",
+        "repeat",
+        "last message repeated 2 times",
+    ]);
+}
@@ -0,0 +1,33 @@
+#![cfg(all(feature = "std", feature = "hook", not(any(feature = "ufmt", feature = "binary", feature = "log_facade"))))]
+
+use rogu::sink::collapse::{self, Collapse, Platform};
+
+use core::time::Duration;
+use std::sync::Mutex;
+
+static WRITES: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+fn hook(bytes: &[u8]) {
+    WRITES.lock().unwrap().push(String::from_utf8_lossy(bytes).into_owned());
+}
+
+static PLATFORM: Collapse<Platform> = Collapse::new(Platform, Duration::from_secs(3600));
+
+#[test]
+fn should_collapse_platform_output() {
+    rogu::set_level(rogu::Level::INFO);
+    rogu::set_write_hook(hook);
+    assert!(collapse::set_platform(&PLATFORM));
+
+    let line = line!() + 2;
+    for _ in 0..3 {
+        rogu::warn!("connection refused");
+    }
+    rogu::info!(target: "audit", "login");
+
+    assert_eq!(*WRITES.lock().unwrap(), [
+        format!("WARN  - [tests/collapse_platform.rs:{}] - connection refused\n", line),
+        format!("WARN  - [tests/collapse_platform.rs:{}] - last message repeated 2 times\n", line),
+        format!("INFO  - [tests/collapse_platform.rs:{}] - audit: login\n", line + 2),
+    ]);
+}
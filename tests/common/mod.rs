@@ -0,0 +1,31 @@
+//!Fixtures, shared by integration tests.
+
+use rogu::sink::{Record, Sink};
+
+use std::sync::{Mutex, MutexGuard};
+
+///Sink, that keeps records formatted by `format`.
+pub struct Capture {
+    format: fn(&Record<'_>) -> String,
+    records: Mutex<Vec<String>>,
+}
+
+impl Capture {
+    pub const fn new(format: fn(&Record<'_>) -> String) -> Self {
+        Self {
+            format,
+            records: Mutex::new(Vec::new()),
+        }
+    }
+
+    pub fn records(&self) -> MutexGuard<'_, Vec<String>> {
+        self.records.lock().unwrap()
+    }
+}
+
+impl Sink for Capture {
+    fn write(&self, record: &Record<'_>) {
+        let record = (self.format)(record);
+        self.records().push(record);
+    }
+}
@@ -0,0 +1,77 @@
+#![cfg(not(any(feature = "ufmt", feature = "log_facade", feature = "binary")))]
+
+mod common;
+
+use common::Capture;
+use rogu::sink;
+use rogu::{context, Level};
+
+use std::sync::Mutex;
+
+static CAPTURE: Capture = Capture::new(|record| {
+    let mut text = record.args().to_string();
+    for field in fields() {
+        text.push(' ');
+        text.push_str(&field);
+    }
+    text
+});
+//Without `std` context is global.
+static SERIAL: Mutex<()> = Mutex::new(());
+
+fn fields() -> Vec<String> {
+    let mut fields = Vec::new();
+    context::for_each(|key, value| fields.push(format!("{}={}", key, value)));
+    fields
+}
+
+#[test]
+fn should_include_context_fields() {
+    let _serial = SERIAL.lock().unwrap_or_else(|error| error.into_inner());
+    sink::add(&CAPTURE);
+    rogu::set_level(Level::INFO);
+
+    let request_id = 42;
+    rogu::info!("before");
+    {
+        let _request = rogu::context!(request_id = request_id, user = "bob");
+        rogu::info!("request");
+        {
+            let _step = rogu::context!(step = 1,);
+            rogu::warn!(target: "audit", "step");
+        }
+        rogu::info!("after step");
+
+        #[cfg(feature = "std")]
+        std::thread::spawn(|| rogu::info!("other thread")).join().expect("join");
+    }
+    rogu::info!("after");
+
+    let mut expected = vec![
+        "before",
+        "request request_id=42 user=bob",
+        "step request_id=42 user=bob step=1",
+        "after step request_id=42 user=bob",
+    ];
+    #[cfg(feature = "std")]
+    expected.push("other thread");
+    expected.push("after");
+    assert_eq!(*CAPTURE.records(), expected);
+}
+
+#[test]
+fn should_not_leak_fields_when_stack_is_busy() {
+    let _serial = SERIAL.lock().unwrap_or_else(|error| error.into_inner());
+
+    let outer = std::cell::RefCell::new(Some(rogu::context!(request_id = 1)));
+    //Stack is busy while fields are visited, e.g. by sink, that creates context while rendering record
+    context::for_each(|_, _| {
+        let nested = rogu::context!(nested = 2);
+        drop(nested);
+        outer.borrow_mut().take();
+    });
+    assert!(fields().is_empty(), "{:?}", fields());
+
+    let _context = rogu::context!(step = 3);
+    assert_eq!(fields(), ["step=3"]);
+}
@@ -0,0 +1,40 @@
+#![cfg(all(feature = "deferred", not(any(feature = "ufmt", feature = "log_facade", feature = "binary"))))]
+
+mod common;
+
+use common::Capture;
+use rogu::sink;
+use rogu::Level;
+
+static CAPTURE: Capture = Capture::new(|record| format!("{:?} {}", record.level(), record.args()));
+
+#[test]
+fn should_write_deferred_records_before_error() {
+    sink::add(&CAPTURE);
+    rogu::set_level(Level::WARN);
+    rogu::deferred::set_level(Level::DEBUG);
+
+    {
+        let _scope = rogu::deferred::scope();
+        rogu::debug!("discarded {}", 1);
+    }
+
+    rogu::trace!("not deferred");
+    for idx in 0..rogu::deferred::MAX_RECORDS + 1 {
+        rogu::debug!("context {}", idx);
+    }
+    rogu::warn!("warn");
+
+    std::thread::spawn(|| {
+        rogu::info!("other thread");
+    }).join().unwrap();
+
+    rogu::error!("error");
+    rogu::error!("second error");
+
+    let mut expected = vec!["WARN warn".to_owned()];
+    expected.extend((1..rogu::deferred::MAX_RECORDS + 1).map(|idx| format!("DEBUG context {}", idx)));
+    expected.push("ERROR error".to_owned());
+    expected.push("ERROR second error".to_owned());
+    assert_eq!(*CAPTURE.records(), expected);
+}
@@ -0,0 +1,39 @@
+#![cfg(all(feature = "std", unix, not(any(feature = "hook", feature = "binary", feature = "log_facade", feature = "stream"))))]
+
+use core::time::Duration;
+use std::process::Command;
+
+fn run_child(name: &str) -> Vec<String> {
+    let output = Command::new(std::env::current_exe().unwrap()).args(["--ignored", "--exact", "--nocapture", name]).output().unwrap();
+    assert!(output.status.success());
+    //Test harness prints test name on the same line
+    String::from_utf8(output.stdout).unwrap().lines().filter_map(|line| line.find("INFO ").map(|idx| line[idx..].to_owned())).collect()
+}
+
+#[test]
+#[ignore]
+fn child_deterministic() {
+    rogu::set_level(rogu::Level::INFO);
+    rogu::set_deterministic(true);
+    rogu::info!("snapshot {}", 1);
+}
+
+#[test]
+#[ignore]
+fn child_mock_clock() {
+    static CLOCK: rogu::clock::Mock = rogu::clock::Mock::stepping(Duration::from_secs(1_600_000_000), Duration::from_secs(61));
+
+    rogu::set_level(rogu::Level::INFO);
+    rogu::clock::set(&CLOCK);
+    rogu::info!("snapshot {}", 1);
+    rogu::info!("snapshot {}", 2);
+}
+
+#[test]
+fn should_produce_reproducible_output() {
+    assert_eq!(run_child("child_deterministic"), ["INFO  - [deterministic.rs:18] - snapshot 1"]);
+    assert_eq!(run_child("child_mock_clock"), [
+        "INFO  [2020-09-13 12:26:40] - [tests/deterministic.rs:28] - snapshot 1",
+        "INFO  [2020-09-13 12:27:41] - [tests/deterministic.rs:29] - snapshot 2",
+    ]);
+}
@@ -0,0 +1,49 @@
+#![cfg(not(any(feature = "ufmt", feature = "log_facade", feature = "binary")))]
+
+mod common;
+
+use common::Capture;
+use rogu::sink;
+use rogu::Level;
+
+static CAPTURE: Capture = Capture::new(|record| format!("{:?} {}", record.level(), record.args()));
+
+fn status_level(status: u16) -> Level {
+    match status {
+        500..=599 => Level::ERROR,
+        400..=499 => Level::WARN,
+        300..=399 => Level::DEBUG,
+        _ => Level::INFO,
+    }
+}
+
+#[cfg(not(feature = "level_info_off"))]
+#[test]
+fn should_write_with_runtime_level() {
+    sink::add(&CAPTURE);
+    rogu::set_level(Level::INFO);
+
+    for status in [200u16, 302, 404, 503].iter() {
+        rogu::log!(status_level(*status), "status {}", status);
+    }
+    rogu::log!(Level::NONE, "never");
+
+    assert!(rogu::enabled!(Level::FATAL));
+    assert!(rogu::enabled!(status_level(200)));
+    assert!(!rogu::enabled!(status_level(302)));
+    assert!(!rogu::enabled!(Level::NONE));
+
+    assert_eq!(*CAPTURE.records(), [
+        "INFO status 200",
+        "WARN status 404",
+        "ERROR status 503",
+    ]);
+}
+
+#[cfg(feature = "level_info_off")]
+#[test]
+fn should_not_enable_level_disabled_by_feature() {
+    rogu::set_level(Level::TRACE);
+    assert!(rogu::is_enabled(Level::INFO));
+    assert!(!rogu::enabled!(Level::INFO));
+}
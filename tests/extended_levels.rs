@@ -0,0 +1,32 @@
+#![cfg(all(feature = "extended_levels", not(any(feature = "ufmt", feature = "log_facade", feature = "binary"))))]
+
+mod common;
+
+use common::Capture;
+use rogu::sink;
+use rogu::Level;
+
+static CAPTURE: Capture = Capture::new(|record| format!("{:?} {}", record.level(), record.args()));
+
+#[test]
+fn should_filter_extended_levels_by_severity() {
+    sink::add(&CAPTURE);
+
+    rogu::set_level(Level::NOTICE);
+    assert!(rogu::is_enabled(Level::WARN));
+    assert!(!rogu::is_enabled(Level::INFO));
+
+    rogu::critical!("disk {} failed", 1);
+    rogu::notice!("failover");
+    rogu::info!("filtered");
+
+    rogu::set_level(Level::CRITICAL);
+    rogu::error!("filtered");
+    rogu::critical!("still written");
+
+    assert_eq!(*CAPTURE.records(), [
+        "CRITICAL disk 1 failed",
+        "NOTICE failover",
+        "CRITICAL still written",
+    ]);
+}
@@ -0,0 +1,65 @@
+#![cfg(all(feature = "std", unix, not(any(feature = "ufmt", feature = "hook", feature = "binary", feature = "log_facade"))))]
+
+use rogu::sink::{self, Record, Sink};
+
+use std::os::unix::process::ExitStatusExt;
+use std::process::{Command, Output};
+
+struct Flushed;
+
+impl Sink for Flushed {
+    fn write(&self, _: &Record<'_>) {
+    }
+
+    fn flush(&self) {
+        eprintln!("sink flushed");
+    }
+}
+
+fn run_child(name: &str) -> Output {
+    Command::new(std::env::current_exe().unwrap()).args(["--ignored", "--exact", "--nocapture", name]).output().unwrap()
+}
+
+#[test]
+#[ignore]
+fn child_abort() {
+    sink::add(&Flushed);
+    rogu::set_level(rogu::Level::NONE);
+    rogu::error!("filtered");
+    rogu::fatal!("unrecoverable {}", 42);
+}
+
+#[test]
+#[ignore]
+fn child_handler() {
+    rogu::set_fatal_handler(|| {
+        eprintln!("handler called");
+        std::process::exit(3);
+    });
+    rogu::set_level(rogu::Level::NONE);
+    let _: u32 = rogu::fatal!("unrecoverable");
+}
+
+#[test]
+fn should_write_fatal_record_before_abort() {
+    let output = run_child("child_abort");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    //SIGABRT
+    assert_eq!(output.status.signal(), Some(6));
+    assert!(!stderr.contains("filtered"));
+    let record = stderr.find("FATAL ").expect("fatal record");
+    assert!(stderr[record..].contains("unrecoverable 42\n"));
+    let flushed = stderr.find("sink flushed").expect("sinks flushed");
+    assert!(record < flushed);
+}
+
+#[test]
+fn should_call_fatal_handler() {
+    let output = run_child("child_handler");
+    let stderr = String::from_utf8(output.stderr).unwrap();
+
+    assert_eq!(output.status.code(), Some(3));
+    let record = stderr.find("FATAL ").expect("fatal record");
+    assert!(record < stderr.find("handler called").expect("handler called"));
+}
@@ -0,0 +1,174 @@
+#![cfg(feature = "http")]
+
+use rogu::sink::{Sink, Record};
+use rogu::sink::http::{Config, HttpSink};
+
+use std::io::{Read, Write, BufRead, BufReader};
+use std::net::TcpListener;
+use std::sync::mpsc;
+use std::time::Duration;
+use std::thread;
+
+//Local stand-in server, which responds with `statuses` in order and then 204
+fn serve(statuses: &'static [u16]) -> (String, mpsc::Receiver<String>) {
+    let listener = TcpListener::bind("127.0.0.1:0").expect("bind");
+    let url = format!("http://{}/push", listener.local_addr().unwrap());
+    let (sender, receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        let mut statuses = statuses.iter();
+        for stream in listener.incoming() {
+            let stream = stream.expect("accept");
+            let mut reader = BufReader::new(stream);
+
+            let mut len = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).expect("read header");
+                if line == "\r\n" {
+                    break;
+                } else if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                    len = value.trim().parse().expect("content-length");
+                }
+            }
+
+            let mut body = vec![0; len];
+            reader.read_exact(&mut body).expect("read body");
+
+            let status = statuses.next().copied().unwrap_or(204);
+            let _ = write!(reader.get_mut(), "HTTP/1.1 {} Status\r\nContent-Length: 0\r\n\r\n", status);
+            let _ = sender.send(String::from_utf8(body).expect("utf-8 body"));
+        }
+    });
+
+    (url, receiver)
+}
+
+fn write(sink: &HttpSink, level: rogu::Level, message: &str) {
+    sink.write(&Record::new(level, file!(), line!(), module_path!(), format_args!("{}", message)));
+}
+
+#[test]
+fn should_push_loki_batch() {
+    let (url, bodies) = serve(&[]);
+    let sink = HttpSink::new(Config::loki(&url).label("app", "test").max_batch_size(10)).expect("create sink");
+
+    write(&sink, rogu::Level::ERROR, "first \"quoted\"");
+    sink.write(&Record::new(rogu::Level::ERROR, file!(), line!(), module_path!(), format_args!("audited")).with_target("audit"));
+    {
+        let _context = rogu::context!(request_id = 7);
+        write(&sink, rogu::Level::INFO, "second");
+    }
+    sink.flush();
+
+    let body = bodies.recv_timeout(Duration::from_secs(5)).expect("to receive batch");
+    assert!(body.starts_with("{\"streams\":[{\"stream\":{\"level\":\"error\",\"target\":\"http_sink\",\"app\":\"test\"},\"values\":[[\""), "{}", body);
+    assert!(body.contains("{\"stream\":{\"level\":\"error\",\"target\":\"audit\",\"app\":\"test\"}"), "{}", body);
+    assert!(body.contains("first \\\"quoted\\\""), "{}", body);
+    assert!(body.contains("{\"stream\":{\"level\":\"info\",\"target\":\"http_sink\",\"app\":\"test\"}"), "{}", body);
+    assert!(body.contains("second"), "{}", body);
+    assert!(body.contains("\"code.namespace\":\"http_sink\""), "{}", body);
+    assert!(body.contains("\"code.lineno\":\""), "{}", body);
+    assert!(body.contains("\"request_id\":\"7\"}]"), "{}", body);
+    assert_eq!(sink.dropped(), 0);
+}
+
+#[test]
+fn should_push_otlp_batch() {
+    let (url, bodies) = serve(&[]);
+    let sink = HttpSink::new(Config::otlp(&url).label("service.name", "test")).expect("create sink");
+
+    write(&sink, rogu::Level::WARN, "otlp");
+    sink.flush();
+
+    let body = bodies.recv_timeout(Duration::from_secs(5)).expect("to receive batch");
+    assert!(body.starts_with("{\"resourceLogs\":[{\"resource\":{\"attributes\":[{\"key\":\"service.name\",\"value\":{\"stringValue\":\"test\"}}]}"), "{}", body);
+    assert!(body.contains("\"severityNumber\":13,\"severityText\":\"WARN\",\"body\":{\"stringValue\":\"otlp\"}"), "{}", body);
+    assert!(body.contains("{\"key\":\"code.filepath\",\"value\":{\"stringValue\":\"tests/http_sink.rs\"}}"), "{}", body);
+    assert!(body.contains("{\"key\":\"code.namespace\",\"value\":{\"stringValue\":\"http_sink\"}}"), "{}", body);
+    assert!(body.contains("{\"key\":\"log.target\",\"value\":{\"stringValue\":\"http_sink\"}}"), "{}", body);
+}
+
+#[test]
+fn should_split_by_max_batch_size() {
+    let (url, bodies) = serve(&[]);
+    let sink = HttpSink::new(Config::loki(&url).max_batch_size(2)).expect("create sink");
+
+    write(&sink, rogu::Level::INFO, "1");
+    write(&sink, rogu::Level::INFO, "2");
+    write(&sink, rogu::Level::INFO, "3");
+    sink.flush();
+
+    let first = bodies.recv_timeout(Duration::from_secs(5)).expect("to receive first batch");
+    let second = bodies.recv_timeout(Duration::from_secs(5)).expect("to receive second batch");
+    assert_eq!(first.matches("[\"").count(), 2, "{}", first);
+    assert_eq!(second.matches("[\"").count(), 1, "{}", second);
+}
+
+#[test]
+fn should_send_batch_after_max_age() {
+    let (url, bodies) = serve(&[]);
+    let sink = HttpSink::new(Config::loki(&url).max_batch_age(Duration::from_millis(50))).expect("create sink");
+
+    write(&sink, rogu::Level::INFO, "aged");
+
+    let body = bodies.recv_timeout(Duration::from_secs(5)).expect("to receive batch");
+    assert!(body.contains("aged"), "{}", body);
+}
+
+#[test]
+fn should_retry_on_server_error() {
+    let (url, bodies) = serve(&[500, 503]);
+    let sink = HttpSink::new(Config::loki(&url).retry(3, Duration::from_millis(1))).expect("create sink");
+
+    write(&sink, rogu::Level::ERROR, "retry me");
+    sink.flush();
+
+    for _ in 0..3 {
+        let body = bodies.recv_timeout(Duration::from_secs(5)).expect("to receive attempt");
+        assert!(body.contains("retry me"), "{}", body);
+    }
+    assert_eq!(sink.dropped(), 0);
+}
+
+#[test]
+fn should_keep_undelivered_records_in_spill() {
+    let (url, bodies) = serve(&[500, 500]);
+    let sink = HttpSink::new(Config::loki(&url).retry(1, Duration::from_millis(1)).spill_capacity(1)).expect("create sink");
+
+    write(&sink, rogu::Level::ERROR, "old");
+    write(&sink, rogu::Level::ERROR, "new");
+    sink.flush();
+
+    //Two failed attempts, after which only newest record remains in spill
+    bodies.recv_timeout(Duration::from_secs(5)).expect("to receive first attempt");
+    bodies.recv_timeout(Duration::from_secs(5)).expect("to receive second attempt");
+    assert_eq!(sink.dropped(), 1);
+
+    sink.flush();
+    let body = bodies.recv_timeout(Duration::from_secs(5)).expect("to receive spilled batch");
+    assert!(!body.contains("old"), "{}", body);
+    assert!(body.contains("new"), "{}", body);
+}
+
+#[test]
+fn should_receive_records_from_macros() {
+    let (url, bodies) = serve(&[]);
+    let sink: &'static HttpSink = Box::leak(Box::new(HttpSink::new(Config::loki(&url)).expect("create sink")));
+    assert!(rogu::sink::add(sink));
+
+    rogu::set_level(rogu::Level::INFO);
+    rogu::info!("from {}", "macro");
+    rogu::debug!("filtered");
+    rogu::sink::flush();
+
+    let body = bodies.recv_timeout(Duration::from_secs(5)).expect("to receive batch");
+    assert!(body.contains("from macro"), "{}", body);
+    assert!(!body.contains("filtered"), "{}", body);
+}
+
+#[test]
+fn should_reject_unsupported_url() {
+    assert!(HttpSink::new(Config::loki("https://localhost/push")).is_err());
+    assert!(HttpSink::new(Config::loki("http://:80/push")).is_err());
+}
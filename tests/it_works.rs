@@ -9,6 +9,7 @@ fn it_works() {
     rogu::warn!("");
     rogu::warn!("warn!");
     rogu::info!("info!");
+    rogu::info!();
     rogu::debug!("debug!");
     rogu::trace!("trace!");
 
@@ -0,0 +1,69 @@
+#![cfg(not(feature = "ufmt"))]
+
+use rogu::{Level, LevelFilter};
+
+#[test]
+fn should_parse_level() {
+    assert_eq!("warn".parse::<Level>(), Ok(Level::WARN));
+    assert_eq!("WARNING".parse::<Level>(), Ok(Level::WARN));
+    assert_eq!("Trace".parse::<Level>(), Ok(Level::TRACE));
+    assert_eq!("3".parse::<Level>(), Ok(Level::ERROR));
+    assert!("off".parse::<Level>().is_err());
+    assert!("0".parse::<Level>().is_err());
+    assert!("9".parse::<Level>().is_err());
+    assert!("verbose".parse::<Level>().is_err());
+
+    assert_eq!("OFF".parse::<LevelFilter>(), Ok(LevelFilter::OFF));
+    assert_eq!("0".parse::<LevelFilter>(), Ok(LevelFilter::OFF));
+    assert_eq!("debug".parse::<LevelFilter>(), Ok(LevelFilter::DEBUG));
+}
+
+#[test]
+fn should_order_and_display_level() {
+    assert!(Level::FATAL < Level::ERROR);
+    assert!(Level::ERROR < Level::TRACE);
+    assert!(Level::INFO <= LevelFilter::INFO);
+    assert!(Level::DEBUG > LevelFilter::INFO);
+    assert!(LevelFilter::OFF < Level::FATAL);
+    assert_eq!(LevelFilter::from(Level::WARN), LevelFilter::WARN);
+    assert_eq!(LevelFilter::WARN.to_level(), Some(Level::WARN));
+    assert_eq!(LevelFilter::OFF.to_level(), None);
+
+    assert_eq!(Level::WARN.to_string(), "WARN");
+    assert_eq!(format!("[{:<5}]", Level::INFO), "[INFO ]");
+    assert_eq!(LevelFilter::OFF.to_string(), "OFF");
+
+    for level in [Level::FATAL, Level::ERROR, Level::WARN, Level::INFO, Level::DEBUG, Level::TRACE].iter() {
+        assert_eq!(level.to_string().parse::<Level>().as_ref(), Ok(level));
+    }
+}
+
+#[test]
+fn should_return_current_level() {
+    rogu::set_level(LevelFilter::OFF);
+    assert_eq!(rogu::level(), LevelFilter::OFF);
+    assert!(!rogu::is_enabled(Level::ERROR));
+    assert!(rogu::is_enabled(Level::FATAL));
+
+    rogu::set_level(Level::DEBUG);
+    assert_eq!(rogu::level(), LevelFilter::DEBUG);
+    assert!(rogu::is_enabled(Level::DEBUG));
+    assert!(!rogu::is_enabled(Level::TRACE));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn should_deserialize_level() {
+    use serde::de::{Deserialize, IntoDeserializer};
+    use serde::de::value::Error;
+
+    fn level<'de, T: Deserialize<'de>, D: IntoDeserializer<'de, Error>>(value: D) -> Result<T, Error> {
+        T::deserialize(value.into_deserializer())
+    }
+
+    assert_eq!(level::<Level, _>("info"), Ok(Level::INFO));
+    assert_eq!(level::<Level, _>(7u64), Ok(Level::DEBUG));
+    assert_eq!(level::<LevelFilter, _>("off"), Ok(LevelFilter::OFF));
+    assert!(level::<Level, _>("off").is_err());
+    assert!(level::<Level, _>(-1i64).is_err());
+}
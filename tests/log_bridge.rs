@@ -0,0 +1,35 @@
+#![cfg(all(feature = "log", not(feature = "ufmt")))]
+
+mod common;
+
+use common::Capture;
+
+static CAPTURE: Capture = Capture::new(|record| format!("{:?} {} {} {}", record.level(), record.target(), record.module(), record.args()));
+
+#[test]
+fn should_forward_log_records() {
+    rogu::set_level(rogu::Level::INFO);
+    assert!(rogu::set_target_level("noisy", rogu::Level::ERROR));
+    assert!(rogu::set_target_level("verbose", rogu::Level::TRACE));
+    assert!(rogu::sink::add(&CAPTURE));
+
+    assert_eq!(log::max_level(), log::LevelFilter::Trace);
+
+    log::info!("plain");
+    log::info!(request = 1, user = "name"; "with {}", "values");
+    log::info!(target: "audit", "audited");
+    log::warn!(target: "noisy::client", "filtered");
+    log::error!(target: "noisy::client", "not filtered");
+    log::info!(target: "noisy_neighbour", "prefix only");
+    log::trace!(target: "verbose::inner", "verbose");
+    log::debug!("filtered");
+
+    assert_eq!(*CAPTURE.records(), [
+        "INFO log_bridge log_bridge plain",
+        "INFO log_bridge log_bridge with values request=1 user=name",
+        "INFO audit log_bridge audited",
+        "ERROR noisy::client log_bridge not filtered",
+        "INFO noisy_neighbour log_bridge prefix only",
+        "TRACE verbose::inner log_bridge verbose",
+    ]);
+}
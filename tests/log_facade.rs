@@ -0,0 +1,47 @@
+#![cfg(feature = "log_facade")]
+
+use std::sync::Mutex;
+
+type Captured = (log::Level, String, Option<String>, Option<u32>, String, Option<String>);
+
+struct Capture(Mutex<Vec<Captured>>);
+
+impl log::Log for Capture {
+    fn enabled(&self, _: &log::Metadata) -> bool {
+        true
+    }
+
+    fn log(&self, record: &log::Record) {
+        self.0.lock().unwrap().push((record.level(), record.target().to_owned(), record.file().map(str::to_owned), record.line(), record.args().to_string(), record.key_values().get(log::kv::Key::from_str("level")).map(|level| level.to_string())));
+    }
+
+    fn flush(&self) {
+    }
+}
+
+static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+
+#[test]
+fn should_forward_macros_into_installed_logger() {
+    log::set_logger(&CAPTURE).expect("install logger");
+    log::set_max_level(log::LevelFilter::Info);
+
+    let line = line!() + 1;
+    rogu::info!("hello {}", 1);
+    rogu::debug!("filtered");
+
+    //Installed logger and its level must be kept
+    rogu::set_level(rogu::Level::TRACE);
+    assert_eq!(log::max_level(), log::LevelFilter::Info);
+    rogu::trace!("filtered");
+    rogu::warn!(target: "audit", "login {}", "user");
+    //`fatal!` aborts, so record is forwarded as macro does
+    rogu::__log_forward(rogu::Level::FATAL, file!(), line + 9, module_path!(), "audit", format_args!("unrecoverable"));
+
+    let records = CAPTURE.0.lock().unwrap();
+    assert_eq!(*records, [
+        (log::Level::Info, "log_facade".to_owned(), Some("tests/log_facade.rs".to_owned()), Some(line), "hello 1".to_owned(), None),
+        (log::Level::Warn, "audit".to_owned(), Some("tests/log_facade.rs".to_owned()), Some(line + 7), "login user".to_owned(), None),
+        (log::Level::Error, "audit".to_owned(), Some("tests/log_facade.rs".to_owned()), Some(line + 9), "unrecoverable".to_owned(), Some("FATAL".to_owned())),
+    ]);
+}
@@ -0,0 +1,33 @@
+#![cfg(all(feature = "std", unix, not(any(feature = "hook", feature = "binary", feature = "log_facade"))))]
+
+use std::process::Command;
+
+const MARKER: &str = "rogu-print-marker";
+
+fn run_child(name: &str) -> String {
+    let output = Command::new(std::env::current_exe().unwrap()).args(["--ignored", "--exact", name]).output().unwrap();
+    assert!(output.status.success());
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+#[ignore]
+fn child_print() {
+    rogu::set_level(rogu::Level::INFO);
+    rogu::set_print(true);
+    rogu::info!("{}", MARKER);
+}
+
+#[test]
+#[ignore]
+fn child_stdio() {
+    rogu::set_level(rogu::Level::INFO);
+    rogu::set_print(false);
+    rogu::info!("{}", MARKER);
+}
+
+#[test]
+fn should_be_captured_by_test_harness() {
+    assert!(!run_child("child_print").contains(MARKER));
+    assert!(run_child("child_stdio").contains(MARKER));
+}
@@ -0,0 +1,75 @@
+#![cfg(not(any(feature = "ufmt", feature = "log_facade", feature = "binary")))]
+
+mod common;
+
+use common::Capture;
+use rogu::sink;
+use rogu::Level;
+
+static CAPTURE: Capture = Capture::new(|record| format!("{:?} {} {}", record.level(), record.target(), record.args()));
+
+#[test]
+fn should_rate_limit_per_callsite() {
+    sink::add(&CAPTURE);
+    rogu::set_level(Level::INFO);
+
+    for idx in 0..5 {
+        rogu::warn_once!("once {}", idx);
+    }
+    for idx in 0..7 {
+        rogu::info_every_n!(3, "every third {}", idx);
+    }
+    rogu::warn_once!("other callsite");
+    rogu::debug_once!("disabled");
+
+    //Disabled record must not consume callsite's state
+    for level in [Level::INFO, Level::DEBUG] {
+        rogu::set_level(level);
+        rogu::debug_once!("once enabled");
+    }
+    rogu::set_level(Level::INFO);
+
+    for idx in 0..3 {
+        rogu::warn_every_n!(2, target: "audit", "audit {}", idx);
+    }
+
+    let expected = vec![
+        "WARN rate once 0",
+        "INFO rate every third 0",
+        "INFO rate every third 3",
+        "INFO rate Suppressed 2 similar records",
+        "INFO rate every third 6",
+        "INFO rate Suppressed 2 similar records",
+        "WARN rate other callsite",
+        "DEBUG rate once enabled",
+        "WARN audit audit 0",
+        "WARN audit audit 2",
+        "WARN audit Suppressed 1 similar records",
+    ];
+
+    #[cfg(feature = "std")]
+    let expected = {
+        use core::time::Duration;
+
+        let mut expected = expected;
+
+        static CLOCK: rogu::clock::Mock = rogu::clock::Mock::fixed(Duration::from_secs(1_600_000_000));
+        rogu::clock::set(&CLOCK);
+
+        for idx in 0..6 {
+            if idx == 3 {
+                CLOCK.advance(Duration::from_secs(10));
+            }
+            rogu::error_every!(Duration::from_secs(10), "every 10s {}", idx);
+        }
+
+        expected.extend_from_slice(&[
+            "ERROR rate every 10s 0",
+            "ERROR rate every 10s 3",
+            "ERROR rate Suppressed 2 similar records",
+        ]);
+        expected
+    };
+
+    assert_eq!(*CAPTURE.records(), expected);
+}
@@ -0,0 +1,128 @@
+#![cfg(all(feature = "ring", not(feature = "ufmt")))]
+
+use rogu::ring::Ring;
+use rogu::sink::{Record, Sink};
+use rogu::Level;
+
+fn write(ring: &Ring<4, 32>, level: Level, message: &str) {
+    ring.write(&Record::new(level, "src/main.rs", 1, "main", format_args!("{}", message)));
+}
+
+#[test]
+fn should_keep_last_records() {
+    static RING: Ring<4, 32> = Ring::new();
+
+    assert_eq!(RING.iter().count(), 0);
+    for idx in 0..6 {
+        write(&RING, Level::INFO, &idx.to_string());
+    }
+    write(&RING, Level::ERROR, "ÿ".repeat(20).as_str());
+
+    let entries = RING.iter().collect::<Vec<_>>();
+    assert_eq!(entries.len(), 4);
+    assert_eq!(entries[0].text(), "INFO  - [src/main.rs:1] - 3");
+    assert_eq!(entries[0].level(), Level::INFO);
+    assert_eq!(entries[3].level(), Level::ERROR);
+    //Truncated on char boundary
+    assert_eq!(entries[3].text(), format!("ERROR - [src/main.rs:1] - {}", "ÿ".repeat(3)));
+
+    let mut dump = String::new();
+    RING.dump(&mut dump).unwrap();
+    assert_eq!(dump.lines().count(), 4);
+    assert!(dump.starts_with("INFO  - [src/main.rs:1] - 3\nINFO  - [src/main.rs:1] - 4\n"));
+}
+
+#[test]
+fn should_survive_concurrent_writers() {
+    static RING: Ring<4, 32> = Ring::new();
+
+    let writers = (0..4).map(|_| std::thread::spawn(|| {
+        for idx in 0..1000 {
+            write(&RING, Level::INFO, &idx.to_string());
+        }
+    })).collect::<Vec<_>>();
+
+    while writers.iter().any(|writer| !writer.is_finished()) {
+        for entry in RING.iter() {
+            assert!(entry.text().starts_with("INFO  - [src/main.rs:1] - "), "{}", entry.text());
+        }
+    }
+
+    assert_eq!(RING.iter().count(), 4);
+}
+
+const STRESS_PREFIX: &str = "INFO  - [src/main.rs:1] - ";
+
+//Payload repeats writer's letter as many times as index says, so that mixed writes are detected.
+struct Payload {
+    writer: usize,
+    idx: usize,
+}
+
+impl std::fmt::Display for Payload {
+    fn fmt(&self, fmt: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let letter = (b'a' + self.writer as u8) as char;
+        write!(fmt, "{} {} ", self.writer, self.idx)?;
+        //Let other writers lap this one in the middle of record
+        std::thread::yield_now();
+        fmt.write_str(&letter.to_string().repeat(self.idx % 16 + 1))
+    }
+}
+
+fn check_stress_records(ring: &Ring<2, 64>, writers: usize) {
+    let mut last = vec![None; writers];
+    for entry in ring.iter() {
+        let text = entry.text();
+        let mut parts = text.strip_prefix(STRESS_PREFIX).expect("prefix").split(' ');
+        let writer = parts.next().and_then(|part| part.parse::<usize>().ok()).expect("writer");
+        let idx = parts.next().and_then(|part| part.parse::<usize>().ok()).expect("index");
+        let payload = parts.next().expect("payload");
+        assert_eq!(parts.next(), None, "{}", text);
+
+        let letter = (b'a' + writer as u8) as char;
+        assert_eq!(payload, letter.to_string().repeat(idx % 16 + 1), "{}", text);
+        //Records of the same writer are ordered from oldest to newest
+        assert!(last[writer] < Some(idx), "{}", text);
+        last[writer] = Some(idx);
+    }
+}
+
+#[test]
+fn should_not_return_torn_or_stale_records() {
+    const WRITERS: usize = 16;
+    static RING: Ring<2, 64> = Ring::new();
+
+    let writers = (0..WRITERS).map(|writer| std::thread::spawn(move || {
+        for idx in 0..2_000 {
+            RING.write(&Record::new(Level::INFO, "src/main.rs", 1, "main", format_args!("{}", Payload { writer, idx })));
+        }
+    })).collect::<Vec<_>>();
+
+    while writers.iter().any(|writer| !writer.is_finished()) {
+        check_stress_records(&RING, WRITERS);
+    }
+
+    for writer in writers {
+        writer.join().expect("join");
+    }
+    check_stress_records(&RING, WRITERS);
+}
+
+#[test]
+fn should_register_global_ring() {
+    rogu::set_level(Level::INFO);
+    rogu::info!("global {}", 1);
+    rogu::debug!("filtered");
+
+    let mut dump = String::new();
+    rogu::ring::dump(&mut dump).unwrap();
+    assert_eq!(dump, format!("INFO  - [tests/ring.rs:{}] - global 1\n", line!() - 5));
+    assert_eq!(rogu::ring::iter().count(), 1);
+}
+
+#[test]
+fn should_use_configured_global_ring_size() {
+    let size = |var: Option<&str>, default: usize| var.map_or(default, |size| size.parse().expect("size"));
+    assert_eq!(rogu::ring::SLOTS, size(option_env!("ROGU_RING_SLOTS"), 64));
+    assert_eq!(rogu::ring::SLOT_SIZE, size(option_env!("ROGU_RING_SLOT_SIZE"), 128));
+}
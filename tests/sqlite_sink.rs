@@ -0,0 +1,66 @@
+#![cfg(feature = "sqlite")]
+
+use rogu::sink::{Sink, Record};
+use rogu::sink::sqlite::{Config, SqliteSink};
+
+use std::path::PathBuf;
+
+fn db_path(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("rogu-{}-{}.sqlite", name, std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+fn write(sink: &SqliteSink, level: rogu::Level, message: &str) {
+    sink.write(&Record::new(level, file!(), 10, module_path!(), format_args!("{}", message)));
+}
+
+#[test]
+fn should_write_records() {
+    let path = db_path("write");
+    let sink = SqliteSink::new(Config::new(&path)).expect("open db");
+
+    write(&sink, rogu::Level::ERROR, "first");
+    sink.write(&Record::new(rogu::Level::WARN, file!(), 10, module_path!(), format_args!("audited")).with_target("audit"));
+    {
+        let _context = rogu::context!(request_id = 42, user = "\"bob\"");
+        write(&sink, rogu::Level::INFO, "second");
+    }
+    sink.flush();
+
+    let connection = rusqlite::Connection::open(&path).expect("open db");
+    let mut query = connection.prepare("SELECT level, module, target, file, line, message, fields FROM logs ORDER BY id").expect("prepare");
+    let rows = query.query_map([], |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?, row.get::<_, String>(2)?, row.get::<_, String>(3)?, row.get::<_, u32>(4)?, row.get::<_, String>(5)?, row.get::<_, String>(6)?)))
+                    .expect("query")
+                    .collect::<Result<Vec<_>, _>>()
+                    .expect("read rows");
+
+    assert_eq!(rows, vec![
+        ("ERROR".to_owned(), "sqlite_sink".to_owned(), "sqlite_sink".to_owned(), "tests/sqlite_sink.rs".to_owned(), 10, "first".to_owned(), "{}".to_owned()),
+        ("WARN".to_owned(), "sqlite_sink".to_owned(), "audit".to_owned(), "tests/sqlite_sink.rs".to_owned(), 10, "audited".to_owned(), "{}".to_owned()),
+        ("INFO".to_owned(), "sqlite_sink".to_owned(), "sqlite_sink".to_owned(), "tests/sqlite_sink.rs".to_owned(), 10, "second".to_owned(), r#"{"request_id":"42","user":"\"bob\""}"#.to_owned()),
+    ]);
+
+    let ts: i64 = connection.query_row("SELECT ts FROM logs LIMIT 1", [], |row| row.get(0)).expect("read ts");
+    assert!(ts > 0);
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn should_retain_max_rows() {
+    let path = db_path("retain");
+    let sink = SqliteSink::new(Config::new(&path).table("retained").max_rows(3).max_batch_size(2)).expect("open db");
+
+    for idx in 0..10 {
+        write(&sink, rogu::Level::WARN, &idx.to_string());
+    }
+    sink.flush();
+
+    let connection = rusqlite::Connection::open(&path).expect("open db");
+    let mut query = connection.prepare("SELECT message FROM retained ORDER BY id").expect("prepare");
+    let rows = query.query_map([], |row| row.get::<_, String>(0)).expect("query").collect::<Result<Vec<_>, _>>().expect("read rows");
+
+    assert_eq!(rows, ["7", "8", "9"]);
+    assert_eq!(sink.dropped(), 0);
+    let _ = std::fs::remove_file(&path);
+}
@@ -0,0 +1,27 @@
+#![cfg(not(feature = "ufmt"))]
+
+use rogu::{Level, LevelFilter};
+
+fn expected() -> LevelFilter {
+    #[cfg(not(debug_assertions))]
+    {
+        if let Some(level) = option_env!("ROGU_RELEASE_MAX_LEVEL") {
+            return level.parse().unwrap();
+        }
+    }
+
+    match option_env!("ROGU_MAX_LEVEL") {
+        Some(level) => level.parse().unwrap(),
+        None => LevelFilter::TRACE,
+    }
+}
+
+#[test]
+fn should_limit_enabled_levels_by_build_environment() {
+    assert_eq!(rogu::STATIC_MAX_LEVEL, expected());
+
+    rogu::set_level(Level::TRACE);
+    for level in [Level::FATAL, Level::ERROR, Level::WARN, Level::INFO, Level::DEBUG, Level::TRACE].iter() {
+        assert_eq!(rogu::is_enabled(*level), *level == Level::FATAL || *level <= rogu::STATIC_MAX_LEVEL, "{}", level);
+    }
+}
@@ -0,0 +1,29 @@
+#![cfg(all(feature = "hook", feature = "stream", not(feature = "binary")))]
+
+use std::sync::Mutex;
+
+static WRITES: Mutex<Vec<Vec<u8>>> = Mutex::new(Vec::new());
+
+fn hook(bytes: &[u8]) {
+    WRITES.lock().unwrap().push(bytes.to_vec());
+}
+
+#[test]
+fn should_pass_long_pieces_directly() {
+    rogu::set_level(rogu::Level::INFO);
+    rogu::set_write_hook(hook);
+
+    let line = line!() + 1;
+    rogu::info!("short");
+    let long = "ü".repeat(1000);
+    rogu::warn!("long {}", long.as_str());
+
+    let writes = WRITES.lock().unwrap();
+    assert_eq!(String::from_utf8_lossy(&writes[0]), format!("INFO  - [tests/stream.rs:{}] - short\n", line));
+
+    //Only piece, that does not fit into prefix buffer, is written separately
+    assert_eq!(writes.len(), 4);
+    assert_eq!(String::from_utf8_lossy(&writes[1]), format!("WARN  - [tests/stream.rs:{}] - long ", line + 2));
+    assert_eq!(writes[2], long.as_bytes());
+    assert_eq!(writes[3], b"\n");
+}
@@ -0,0 +1,32 @@
+#![cfg(not(any(feature = "ufmt", feature = "log_facade", feature = "binary")))]
+
+mod common;
+
+use common::Capture;
+use rogu::sink;
+use rogu::Level;
+
+static CAPTURE: Capture = Capture::new(|record| format!("{:?} {} {} {}", record.level(), record.target(), record.module(), record.args()));
+
+#[test]
+fn should_write_explicit_target() {
+    sink::add(&CAPTURE);
+    rogu::set_level(Level::INFO);
+    rogu::set_target_level("audit", Level::WARN);
+    rogu::set_target_level("access", Level::DEBUG);
+
+    let channel = String::from("access");
+    rogu::info!("application");
+    rogu::info!(target: "audit", "filtered");
+    rogu::warn!(target: "audit", "login {}", "user");
+    rogu::debug!(target: channel.as_str(), "GET /");
+    rogu::debug!("filtered");
+    rogu::warn_once!(target: "audit", "once");
+
+    assert_eq!(*CAPTURE.records(), [
+        "INFO target target application",
+        "WARN audit target login user",
+        "DEBUG access target GET /",
+        "WARN audit target once",
+    ]);
+}
@@ -0,0 +1,50 @@
+#![cfg(all(feature = "testing", not(feature = "ufmt")))]
+
+use rogu::Level;
+use rogu::testing::{capture, Capture};
+
+#[test]
+fn should_capture_records_of_current_thread() {
+    rogu::set_level(Level::DEBUG);
+
+    let line = line!() + 2;
+    let records = capture(|| {
+        rogu::warn!("connection timeout after {}s", 5);
+        rogu::trace!("filtered");
+        std::thread::spawn(|| rogu::info!("other thread")).join().unwrap();
+
+        rogu::assert_logged!(Level::WARN, contains "timeout");
+    });
+
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].level, Level::WARN);
+    assert_eq!(records[0].file, "tests/testing.rs");
+    assert_eq!(records[0].line, line);
+    assert_eq!(records[0].module, "testing");
+    assert_eq!(records[0].target, "testing");
+    rogu::assert_logged!(records, Level::WARN, "connection timeout after 5s");
+    rogu::assert_logged!(records, Level::WARN, contains String::from("after 5"));
+}
+
+#[test]
+fn should_capture_into_innermost_guard() {
+    rogu::set_level(Level::DEBUG);
+
+    let outer = Capture::start();
+    rogu::info!("outer");
+    let inner = capture(|| rogu::debug!("inner"));
+    assert_eq!(inner.len(), 1);
+
+    let records = outer.finish();
+    assert_eq!(records.len(), 1);
+    rogu::assert_logged!(records, Level::INFO, "outer");
+}
+
+#[test]
+#[should_panic(expected = "No ERROR record containing \"timeout\" among captured:\n  WARN  - [tests/testing.rs:")]
+fn should_panic_when_not_logged() {
+    rogu::set_level(Level::DEBUG);
+
+    let records = capture(|| rogu::warn!("timeout"));
+    rogu::assert_logged!(records, Level::ERROR, contains "timeout");
+}
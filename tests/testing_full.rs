@@ -0,0 +1,21 @@
+#![cfg(all(feature = "testing", not(feature = "ufmt")))]
+
+use rogu::sink::{self, Record, Sink};
+
+struct Discard;
+
+impl Sink for Discard {
+    fn write(&self, _: &Record<'_>) {
+    }
+}
+
+#[test]
+fn should_fail_every_capture_while_sink_is_not_registered() {
+    static DISCARD: Discard = Discard;
+    while sink::add(&DISCARD) {
+    }
+
+    for _ in 0..2 {
+        assert!(std::panic::catch_unwind(|| rogu::testing::capture(|| ())).is_err());
+    }
+}
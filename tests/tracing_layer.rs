@@ -0,0 +1,40 @@
+#![cfg(all(feature = "tracing", not(feature = "ufmt")))]
+
+mod common;
+
+use common::Capture;
+use tracing_subscriber::layer::SubscriberExt;
+
+static CAPTURE: Capture = Capture::new(|record| format!("{:?} {}", record.level(), record.args()));
+
+#[test]
+fn should_write_tracing_events() {
+    rogu::set_level(rogu::Level::INFO);
+    assert!(rogu::sink::add(&CAPTURE));
+
+    let subscriber = tracing_subscriber::registry().with(rogu::tracing::Layer);
+    tracing::subscriber::with_default(subscriber, || {
+        let request = tracing::info_span!("request", id = 1);
+        let _request = request.enter();
+        let handle = tracing::info_span!("handle");
+        let _handle = handle.enter();
+
+        tracing::error!(code = 500, "failed {}", "request");
+        tracing::debug!("filtered");
+        tracing::warn!(reason = "timeout");
+
+        rogu::set_target_level("audit", rogu::Level::WARN);
+        tracing::info!(target: "audit", "filtered by target");
+        tracing::warn!(target: "audit", "audited");
+
+        rogu::set_level(rogu::Level::DEBUG);
+        tracing::debug!("enabled at runtime");
+    });
+
+    assert_eq!(*CAPTURE.records(), [
+        "ERROR request{id=1}:handle: failed request code=500",
+        "WARN request{id=1}:handle: reason=\"timeout\"",
+        "WARN request{id=1}:handle: audited",
+        "DEBUG request{id=1}:handle: enabled at runtime",
+    ]);
+}
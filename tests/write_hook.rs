@@ -0,0 +1,33 @@
+#![cfg(all(feature = "hook", not(feature = "binary")))]
+
+use std::sync::Mutex;
+
+static WRITES: Mutex<Vec<Vec<u8>>> = Mutex::new(Vec::new());
+
+fn hook(bytes: &[u8]) {
+    WRITES.lock().unwrap().push(bytes.to_vec());
+}
+
+#[test]
+fn should_write_records_into_hook() {
+    rogu::set_level(rogu::Level::INFO);
+    //Records are discarded without hook.
+    rogu::info!("lost");
+
+    rogu::set_write_hook(hook);
+    let line = line!() + 1;
+    rogu::info!("hello {}", "hook");
+    rogu::debug!("filtered");
+
+    let long = "x".repeat(1000);
+    rogu::error!("{}", long);
+
+    let writes = WRITES.lock().unwrap();
+    assert_eq!(String::from_utf8_lossy(&writes[0]), format!("INFO  - [tests/write_hook.rs:{}] - hello hook\n", line));
+
+    //Long record is split into several lines, with continuation marked
+    assert!(writes.len() > 2);
+    let error = writes[1..].iter().map(|write| String::from_utf8_lossy(write)).collect::<String>();
+    let error = error.replace("\n... ", "");
+    assert_eq!(error, format!("ERROR - [tests/write_hook.rs:{}] - {}\n", line + 4, long));
+}